@@ -0,0 +1,445 @@
+use std::{
+    io,
+    process::{self, Stdio},
+    thread,
+};
+
+use crate::config::{AlertRuleConfig, NetworkConfig, RateUnit, UnitPrefix};
+use crate::data::system_stats::{
+    format_bytes_per_sec, mount_points, parse_bytes, InterfaceFilter, Subsystems, SystemStats,
+};
+use crate::ui::dashboard::Panel;
+
+/// A system metric an alert rule watches
+#[derive(Clone, Debug, PartialEq)]
+pub enum Metric {
+    /// Total CPU usage in percent
+    Cpu,
+    /// Memory usage in percent
+    Memory,
+    /// Swap usage in percent
+    Swap,
+    /// 1-minute load average
+    Load,
+    /// Hottest temperature sensor in °C
+    Temperature,
+    /// Received bytes per second on one interface, or all the network panel shows
+    Rx(Option<String>),
+    /// Transmitted bytes per second on one interface, or all the network panel shows
+    Tx(Option<String>),
+    /// Fullness of the filesystem at a mount point in percent
+    Filesystem(String),
+}
+
+impl Metric {
+    fn parse(name: &str, argument: Option<&str>) -> Result<Self, String> {
+        let metric = match (name.to_ascii_lowercase().as_str(), argument) {
+            ("cpu", None) => Metric::Cpu,
+            ("memory" | "mem", None) => Metric::Memory,
+            ("swap", None) => Metric::Swap,
+            ("load", None) => Metric::Load,
+            ("temp" | "temperature", None) => Metric::Temperature,
+            ("rx", interface) => Metric::Rx(interface.map(String::from)),
+            ("tx", interface) => Metric::Tx(interface.map(String::from)),
+            ("filesystem" | "fs", Some(mount)) => Metric::Filesystem(mount.to_string()),
+            ("filesystem" | "fs", None) => return Err("filesystem needs a mount point".to_string()),
+            (_, Some(argument)) if is_known(name) => {
+                return Err(format!("unexpected '{argument}' after '{name}'"));
+            }
+            _ => return Err(format!("unknown metric '{name}'")),
+        };
+        Ok(metric)
+    }
+
+    /// Current value, or `None` if the snapshot does not have it
+    fn value(&self, stats: &SystemStats, interfaces: &InterfaceFilter) -> Option<f64> {
+        let percentage = |used: u64, total: u64| (total > 0).then(|| used as f64 / total as f64 * 100.0);
+        let traffic = |interface: &Option<String>, rx: bool| {
+            let shown = stats.interfaces.iter().filter(|reading| match interface {
+                Some(name) => &reading.name == name,
+                None => interfaces.shows(&reading.name),
+            });
+            shown.map(|reading| if rx { reading.rx } else { reading.tx }).sum::<u64>() as f64
+        };
+
+        match self {
+            Metric::Cpu => Some(stats.cpu_usage),
+            Metric::Memory => percentage(stats.memory_used, stats.memory_total),
+            Metric::Swap => percentage(stats.swap_used, stats.swap_total),
+            Metric::Load => Some(stats.load_average[0]),
+            Metric::Temperature => stats.sensors.iter().map(|sensor| sensor.temperature).reduce(f64::max),
+            Metric::Rx(interface) => Some(traffic(interface, true)),
+            Metric::Tx(interface) => Some(traffic(interface, false)),
+            Metric::Filesystem(mount) => stats
+                .filesystems
+                .iter()
+                .find(|fs| &fs.mount_point == mount)
+                .map(|fs| fs.used_percentage()),
+        }
+    }
+
+    /// Was the metric refreshed in this snapshot?
+    fn is_sampled(&self, sampled: Subsystems) -> bool {
+        let needed = self.subsystems();
+        (!needed.cpu || sampled.cpu)
+            && (!needed.memory || sampled.memory)
+            && (!needed.network || sampled.network)
+            && (!needed.sensors || sampled.sensors)
+            && (!needed.disks || sampled.disks)
+    }
+
+    fn subsystems(&self) -> Subsystems {
+        let mut subsystems = Subsystems::default();
+        match self {
+            Metric::Cpu | Metric::Load => subsystems.cpu = true,
+            Metric::Memory | Metric::Swap => subsystems.memory = true,
+            Metric::Temperature => subsystems.sensors = true,
+            Metric::Rx(_) | Metric::Tx(_) => subsystems.network = true,
+            Metric::Filesystem(_) => subsystems.disks = true,
+        }
+        subsystems
+    }
+
+    /// Panel showing the metric
+    pub fn panel(&self) -> Panel {
+        match self {
+            Metric::Cpu | Metric::Load => Panel::Cpu,
+            Metric::Memory | Metric::Swap => Panel::Memory,
+            Metric::Temperature => Panel::Sensors,
+            Metric::Rx(_) | Metric::Tx(_) => Panel::Network,
+            Metric::Filesystem(_) => Panel::Disks,
+        }
+    }
+
+    fn format(&self, value: f64) -> String {
+        match self {
+            Metric::Cpu | Metric::Memory | Metric::Swap | Metric::Filesystem(_) => format!("{value:.1}%"),
+            Metric::Load => format!("{value:.2}"),
+            Metric::Temperature => format!("{value:.0}°C"),
+            Metric::Rx(_) | Metric::Tx(_) => {
+                format_bytes_per_sec(value.max(0.0) as u64, RateUnit::Bytes, UnitPrefix::Iec)
+            }
+        }
+    }
+}
+
+fn is_known(name: &str) -> bool {
+    ["cpu", "memory", "mem", "swap", "load", "temp", "temperature"].contains(&name.to_ascii_lowercase().as_str())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comparison {
+    Above,
+    AtLeast,
+    Below,
+    AtMost,
+}
+
+impl Comparison {
+    fn parse(op: &str) -> Option<Self> {
+        match op {
+            ">" => Some(Comparison::Above),
+            ">=" => Some(Comparison::AtLeast),
+            "<" => Some(Comparison::Below),
+            "<=" => Some(Comparison::AtMost),
+            _ => None,
+        }
+    }
+
+    fn holds(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Above => value > threshold,
+            Comparison::AtLeast => value >= threshold,
+            Comparison::Below => value < threshold,
+            Comparison::AtMost => value <= threshold,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Above => ">",
+            Comparison::AtLeast => ">=",
+            Comparison::Below => "<",
+            Comparison::AtMost => "<=",
+        }
+    }
+}
+
+/// A parsed alert rule
+#[derive(Clone, Debug)]
+pub struct AlertRule {
+    pub name: String,
+    pub metric: Metric,
+    comparison: Comparison,
+    threshold: f64,
+    /// Seconds the condition must hold before the rule fires
+    for_secs: f64,
+    pub banner: bool,
+    pub command: Option<String>,
+}
+
+impl AlertRule {
+    /// Filesystem rules must name one of `mounts`
+    pub fn from_config(config: &AlertRuleConfig, mounts: &[String]) -> Result<Self, String> {
+        let mut words: Vec<&str> = config.rule.split_whitespace().collect();
+
+        // Optional trailing "for 10s"
+        let mut for_secs = 0.0;
+        if let [.., "for", duration] = words[..] {
+            for_secs = parse_duration(duration)?;
+            words.truncate(words.len() - 2);
+        }
+
+        let (metric, argument, op, value) = match words[..] {
+            [metric, op, value] => (metric, None, op, value),
+            [metric, argument, op, value] => (metric, Some(argument), op, value),
+            _ => return Err("expected e.g. 'cpu > 90% for 10s'".to_string()),
+        };
+
+        let metric = Metric::parse(metric, argument)?;
+        if let Metric::Filesystem(mount) = &metric {
+            if !mounts.contains(mount) {
+                return Err(format!("no filesystem mounted at '{mount}'"));
+            }
+        }
+        let comparison = Comparison::parse(op).ok_or_else(|| format!("unknown comparison '{op}'"))?;
+        let threshold = match metric {
+            Metric::Rx(_) | Metric::Tx(_) => parse_rate(value)?,
+            _ => value
+                .trim_end_matches(['%', 'C', '°'])
+                .parse()
+                .map_err(|_| format!("invalid threshold '{value}'"))?,
+        };
+
+        Ok(Self {
+            name: config.name.clone().unwrap_or_else(|| config.rule.trim().to_string()),
+            metric,
+            comparison,
+            threshold,
+            for_secs,
+            banner: config.banner,
+            command: config.command.clone(),
+        })
+    }
+
+    /// What the rule checks, with a value, e.g. "cpu 95.0% > 90.0%"
+    fn describe(&self, value: f64) -> String {
+        let metric = match &self.metric {
+            Metric::Cpu => "cpu".to_string(),
+            Metric::Memory => "memory".to_string(),
+            Metric::Swap => "swap".to_string(),
+            Metric::Load => "load".to_string(),
+            Metric::Temperature => "temp".to_string(),
+            Metric::Rx(interface) => format!("rx {}", interface.as_deref().unwrap_or("")).trim_end().to_string(),
+            Metric::Tx(interface) => format!("tx {}", interface.as_deref().unwrap_or("")).trim_end().to_string(),
+            Metric::Filesystem(mount) => format!("filesystem {mount}"),
+        };
+        format!(
+            "{metric} {} {} {}",
+            self.metric.format(value),
+            self.comparison.symbol(),
+            self.metric.format(self.threshold)
+        )
+    }
+}
+
+/// `10s`, `2m` or a plain number of seconds
+fn parse_duration(text: &str) -> Result<f64, String> {
+    let (number, scale) = match text.strip_suffix('m') {
+        Some(minutes) => (minutes, 60.0),
+        None => (text.strip_suffix('s').unwrap_or(text), 1.0),
+    };
+    number
+        .parse::<f64>()
+        .map(|value| value * scale)
+        .map_err(|_| format!("invalid duration '{text}'"))
+}
+
+/// A rate such as `50MB/s`, `1.5GiB/s` or `100Mbit/s`, in bytes per second
+fn parse_rate(text: &str) -> Result<f64, String> {
+    let amount = text.strip_suffix("/s").unwrap_or(text);
+    parse_bytes(amount).map_err(|_| format!("invalid rate '{text}' (expected e.g. 50MB/s)"))
+}
+
+/// A rule firing or recovering
+#[derive(Clone, Debug)]
+pub enum AlertEvent {
+    /// Index of the rule and a description of the breach
+    Fired(usize, String),
+    Recovered(usize, String),
+}
+
+struct RuleState {
+    rule: AlertRule,
+    /// Seconds the condition has held so far, by the sampler's clock
+    held: f64,
+    firing: bool,
+}
+
+/// Evaluates the alert rules against each system snapshot
+pub struct AlertMonitor {
+    rules: Vec<RuleState>,
+    /// Interfaces summed by rules without one, as in the network panel
+    interfaces: InterfaceFilter,
+}
+
+impl AlertMonitor {
+    pub fn new(configs: &[AlertRuleConfig], network: &NetworkConfig) -> io::Result<Self> {
+        let mounts = if configs.is_empty() { Vec::new() } else { mount_points() };
+        let rules = configs
+            .iter()
+            .map(|config| {
+                let rule = AlertRule::from_config(config, &mounts).map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidInput, format!("alert rule '{}': {e}", config.rule))
+                })?;
+                Ok(RuleState {
+                    rule,
+                    held: 0.0,
+                    firing: false,
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            rules,
+            interfaces: InterfaceFilter::with_config(network),
+        })
+    }
+
+    pub fn rule(&self, index: usize) -> &AlertRule {
+        &self.rules[index].rule
+    }
+
+    /// Parts of the system the rules need sampled
+    pub fn subsystems(&self) -> Subsystems {
+        self.rules
+            .iter()
+            .fold(Subsystems::default(), |all, state| all.union(state.rule.metric.subsystems()))
+    }
+
+    /// Panels with a rule currently firing
+    pub fn firing_panels(&self) -> Vec<Panel> {
+        self.rules
+            .iter()
+            .filter(|state| state.firing)
+            .map(|state| state.rule.metric.panel())
+            .collect()
+    }
+
+    /// Check every rule whose metric is in the snapshot
+    pub fn evaluate(&mut self, stats: &SystemStats) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+
+        for (index, state) in self.rules.iter_mut().enumerate() {
+            let rule = &state.rule;
+            if !rule.metric.is_sampled(stats.sampled) {
+                continue;
+            }
+            let Some(value) = rule.metric.value(stats, &self.interfaces) else {
+                continue;
+            };
+
+            if rule.comparison.holds(value, rule.threshold) {
+                state.held += stats.elapsed;
+                if !state.firing && state.held >= rule.for_secs {
+                    state.firing = true;
+                    events.push(AlertEvent::Fired(index, rule.describe(value)));
+                }
+            } else {
+                state.held = 0.0;
+                if state.firing {
+                    state.firing = false;
+                    events.push(AlertEvent::Recovered(index, rule.describe(value)));
+                }
+            }
+        }
+
+        events
+    }
+}
+
+/// Run a shell command in the background with extra environment variables;
+/// its output is discarded
+pub fn spawn_shell(command: &str, env: &[(&str, &str)]) -> io::Result<()> {
+    let mut child = process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .envs(env.iter().copied())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // Reap the child so it does not linger as a zombie
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(text: &str) -> Result<AlertRule, String> {
+        let config = AlertRuleConfig {
+            rule: text.to_string(),
+            name: None,
+            banner: false,
+            command: None,
+        };
+        AlertRule::from_config(&config, &["/".to_string(), "/home".to_string()])
+    }
+
+    #[test]
+    fn parses_rules() {
+        let cpu = rule("cpu > 90% for 10s").unwrap();
+        assert_eq!(cpu.metric, Metric::Cpu);
+        assert_eq!((cpu.comparison, cpu.threshold, cpu.for_secs), (Comparison::Above, 90.0, 10.0));
+        assert_eq!(cpu.name, "cpu > 90% for 10s");
+
+        let temp = rule("TEMP >= 85°C for 2m").unwrap();
+        assert_eq!(temp.metric, Metric::Temperature);
+        assert_eq!((temp.comparison, temp.threshold, temp.for_secs), (Comparison::AtLeast, 85.0, 120.0));
+
+        let rx = rule("rx eth0 > 50MB/s").unwrap();
+        assert_eq!(rx.metric, Metric::Rx(Some("eth0".to_string())));
+        assert_eq!((rx.threshold, rx.for_secs), (50e6, 0.0));
+        assert_eq!(rule("tx <= 100Mbit/s").unwrap().metric, Metric::Tx(None));
+
+        let fs = rule("fs /home < 5").unwrap();
+        assert_eq!(fs.metric, Metric::Filesystem("/home".to_string()));
+        assert_eq!(fs.comparison, Comparison::Below);
+    }
+
+    #[test]
+    fn uses_the_configured_name() {
+        let config = AlertRuleConfig {
+            rule: "  load > 4 ".to_string(),
+            name: Some("BUSY".to_string()),
+            banner: true,
+            command: None,
+        };
+        assert_eq!(AlertRule::from_config(&config, &[]).unwrap().name, "BUSY");
+        assert_eq!(rule("  load > 4 ").unwrap().name, "load > 4");
+    }
+
+    #[test]
+    fn rejects_bad_rules() {
+        let cases = [
+            ("cpu", "expected e.g. 'cpu > 90% for 10s'"),
+            ("cpu > 90% for", "unexpected '>' after 'cpu'"),
+            ("disk > 90%", "unknown metric 'disk'"),
+            ("cpu0 > 90%", "unknown metric 'cpu0'"),
+            ("cpu 0 > 90%", "unexpected '0' after 'cpu'"),
+            ("fs > 90%", "filesystem needs a mount point"),
+            ("fs >= 90% for 1m", "filesystem needs a mount point"),
+            ("fs /mnt/nope > 90%", "no filesystem mounted at '/mnt/nope'"),
+            ("cpu == 90%", "unknown comparison '=='"),
+            ("cpu > ninety", "invalid threshold 'ninety'"),
+            ("cpu > 90% for soon", "invalid duration 'soon'"),
+            ("rx > 50MB/min", "invalid rate '50MB/min' (expected e.g. 50MB/s)"),
+        ];
+        for (text, error) in cases {
+            assert_eq!(rule(text).unwrap_err(), error, "{text}");
+        }
+    }
+}
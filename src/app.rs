@@ -1,19 +1,40 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Margin, Rect};
+use std::io;
 
+use crate::command::Command;
+use crate::config::{Config, CountdownConfig, CountdownMode, ProcessSort, Projection};
+use crate::scenario::Scenario;
+use crate::ui::dashboard::{DashboardLayout, Panel};
+use crate::ui::layout::panel_areas;
+use crate::ui::theme::Theme;
 use crate::widgets::{
+    big_text::FontSet,
     clock::ClockState,
-    countdown_timer::CountdownState,
+    countdown_timer::{CountdownState, TimersState},
     cpu_gauge::CpuGaugeState,
-    fake_logs::FakeLogsState,
+    disk_monitor::DiskMonitorState,
+    fake_logs::{FakeLogsState, LogLevel},
+    globe::GlobeState,
     hex_dump::HexDumpState,
     matrix_rain::MatrixRainState,
     memory_gauge::MemoryGaugeState,
     network_monitor::NetworkMonitorState,
+    process_table::ProcessTableState,
     progress_bars::ProgressBarsState,
+    self_destruct::SelfDestructState,
+    sensors::SensorsState,
     source_code::SourceCodeState,
     world_map::WorldMapState,
 };
-use crate::data::system_stats::SystemStats;
+use crate::alerts::{spawn_shell, AlertEvent, AlertMonitor};
+use crate::data::{
+    catalogue::Catalogue,
+    live_connections::{GeoIp, LiveConnections, LIVE_INTERVAL_SECS},
+    sampler::{LiveSampler, StatsSampler},
+    system_stats::{Subsystems, SystemStats},
+    time_source::TimeSource,
+};
 
 /// Application state
 pub struct App {
@@ -29,9 +50,40 @@ pub struct App {
     pub animation_speed: f32,
     /// Show help overlay
     pub show_help: bool,
+    /// Full-screen banner from an alert rule or a command
+    pub banner: Option<Banner>,
+    /// Ring the terminal bell after the next frame
+    pub bell: bool,
+    /// Text typed at the `:` command prompt, if open
+    pub command_input: Option<String>,
+    /// Panel receiving panel-specific keys and highlighted
+    pub focus: Option<Panel>,
+    /// Show the focused panel full screen
+    pub zoomed: bool,
+    /// Last mouse position while dragging the map
+    drag_from: Option<(u16, u16)>,
+    /// Bundled and configured dashboard layouts
+    pub layouts: Vec<DashboardLayout>,
+    /// Index of the layout on screen
+    pub layout: usize,
+    /// Color scheme of the dashboard
+    pub theme: Theme,
+    /// Scripted cues being played
+    pub scenario: Option<Scenario>,
+
+    /// Real or virtual wall-clock time
+    pub time: TimeSource,
 
     // System stats
+    /// Newest snapshot from the sampler
     pub system_stats: SystemStats,
+    sampler: StatsSampler,
+    /// Parts of the system the visible panels need
+    wanted: Subsystems,
+    /// Threshold rules over the system stats
+    pub alerts: AlertMonitor,
+    /// Real connection sampler for the live map mode
+    pub live_connections: Option<LiveSampler>,
 
     // Widget states
     pub matrix_state: MatrixRainState,
@@ -40,35 +92,123 @@ pub struct App {
     pub cpu_state: CpuGaugeState,
     pub memory_state: MemoryGaugeState,
     pub network_state: NetworkMonitorState,
+    pub sensors_state: SensorsState,
+    pub disk_state: DiskMonitorState,
+    pub process_state: ProcessTableState,
     pub map_state: WorldMapState,
-    pub countdown_state: CountdownState,
+    pub globe_state: GlobeState,
+    pub timers_state: TimersState,
     pub clock_state: ClockState,
     pub hex_state: HexDumpState,
     pub progress_state: ProgressBarsState,
+    pub self_destruct: SelfDestructState,
+    /// Big digit fonts for the countdown and clock
+    pub fonts: FontSet,
 }
 
 impl App {
-    pub fn new() -> Self {
-        Self {
+    pub fn new(config: &Config) -> io::Result<Self> {
+        let time = TimeSource::new(&config.time)?;
+        let layouts = DashboardLayout::load_all(&config.layouts)?;
+        let layout_name = config.layout.as_deref().unwrap_or("default");
+        let layout = DashboardLayout::find(&layouts, layout_name).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("unknown layout '{layout_name}'"))
+        })?;
+        // sysinfo needs this long between CPU refreshes for a usable reading
+        let interval = config.sampling.interval_secs;
+        let minimum = sysinfo::MINIMUM_CPU_UPDATE_INTERVAL.as_secs_f64();
+        if !interval.is_finite() || interval < minimum {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("sampling.interval_secs must be at least {minimum}"),
+            ));
+        }
+        let alerts = AlertMonitor::new(&config.alerts, &config.network)?;
+        let wanted = subsystems_for(&layouts[layout].panels()).union(alerts.subsystems());
+        let catalogue = Catalogue::load(&config.map)?;
+        let mut map_state = WorldMapState::with_config(&config.map, &catalogue)?;
+        let live_connections = match &config.map.geoip {
+            Some(path) => Some(LiveSampler::new(
+                LiveConnections::new(GeoIp::open(path)?, catalogue),
+                LIVE_INTERVAL_SECS,
+            )),
+            None => None,
+        };
+        map_state.update_time(time.now());
+        let timers: Vec<CountdownConfig> = config.countdown.iter().chain(&config.timers).cloned().collect();
+        let timers_state = TimersState::with_config(&timers, time.now())?;
+        for timer in &timers_state.timers {
+            if let Some(name) = &timer.expiry.layout {
+                if DashboardLayout::find(&layouts, name).is_none() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("{}: expiry: unknown layout '{name}'", timer.title()),
+                    ));
+                }
+            }
+        }
+        let mut clock_state = ClockState::with_config(&config.clock, &map_state.nodes)?;
+        clock_state.update_time(time.now());
+        let scenario = config.scenario.as_deref().map(Scenario::load).transpose()?;
+        if let (Some(scenario), Some(path)) = (&scenario, &config.scenario) {
+            // Names are only looked up when a cue runs, so check them before the show
+            scenario
+                .check(|command| match command {
+                    Command::Layout(name) => DashboardLayout::find(&layouts, name)
+                        .map(|_| ())
+                        .ok_or_else(|| format!("unknown layout '{name}'")),
+                    Command::Connect(from, to) => map_state.find_node(from).and(map_state.find_node(to)).map(|_| ()),
+                    Command::Hold(Some(name)) | Command::Resume(Some(name)) | Command::Start(Some(name)) => {
+                        timers_state.find(name).map(|_| ()).ok_or_else(|| format!("unknown timer '{name}'"))
+                    }
+                    _ => Ok(()),
+                })
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, format!("{}: {e}", path.display())))?;
+        }
+        let mut logs_state = FakeLogsState::new();
+        logs_state.set_locations(map_state.nodes.iter().map(|node| node.name.clone()).collect());
+
+        Ok(Self {
             running: true,
             paused: false,
             tick_count: 0,
             terminal_size: (80, 24),
             animation_speed: 1.0,
             show_help: false,
-            system_stats: SystemStats::new(),
-            matrix_state: MatrixRainState::new(),
-            logs_state: FakeLogsState::new(),
+            banner: None,
+            bell: false,
+            command_input: None,
+            focus: None,
+            zoomed: false,
+            drag_from: None,
+            layouts,
+            layout,
+            theme: Theme::default(),
+            scenario,
+            time,
+            system_stats: SystemStats::default(),
+            sampler: StatsSampler::new(interval, wanted),
+            wanted,
+            alerts,
+            live_connections,
+            matrix_state: MatrixRainState::with_config(&config.matrix),
+            logs_state,
             source_state: SourceCodeState::new(),
             cpu_state: CpuGaugeState::new(),
             memory_state: MemoryGaugeState::new(),
-            network_state: NetworkMonitorState::new(),
-            map_state: WorldMapState::new(),
-            countdown_state: CountdownState::new(300), // 5 minute countdown
-            clock_state: ClockState::new(),
+            network_state: NetworkMonitorState::with_config(&config.network),
+            sensors_state: SensorsState::new(),
+            disk_state: DiskMonitorState::with_config(&config.disks),
+            process_state: ProcessTableState::with_config(&config.processes),
+            map_state,
+            globe_state: GlobeState::with_config(&config.globe),
+            timers_state,
+            clock_state,
             hex_state: HexDumpState::new(),
-            progress_state: ProgressBarsState::new(),
-        }
+            progress_state: ProgressBarsState::with_config(&config.progress)?,
+            self_destruct: SelfDestructState::new(),
+            fonts: FontSet::with_config(&config.font)?,
+        })
     }
 
     /// Process a tick - update all animations
@@ -78,13 +218,29 @@ impl App {
         }
 
         self.tick_count += 1;
+        self.time.tick();
+        let now = self.time.now();
+        self.clock_state.update_time(now);
+        self.map_state.update_time(now);
+        self.timers_state.update_time(now);
+
+        // Only sample what the panels on screen and the alert rules need
+        let visible = match self.zoomed_panel() {
+            Some(panel) => subsystems_for(&[panel]),
+            None => subsystems_for(&self.current_layout().panels()),
+        };
+        let wanted = visible.union(self.alerts.subsystems());
+        if wanted != self.wanted {
+            self.wanted = wanted;
+            self.sampler.set_wanted(wanted);
+        }
 
-        // Update system stats every ~60 ticks (1 second at 60 FPS)
-        if self.tick_count % 60 == 0 {
-            self.system_stats.refresh();
-            self.cpu_state.update(&self.system_stats);
-            self.memory_state.update(&self.system_stats);
-            self.network_state.update(&self.system_stats);
+        if let Some(stats) = self.sampler.latest() {
+            self.apply_stats(stats);
+        }
+
+        if let Some(snapshot) = self.live_connections.as_ref().and_then(LiveSampler::latest) {
+            self.map_state.apply_live(&snapshot);
         }
 
         // Update animated widgets
@@ -92,10 +248,137 @@ impl App {
         self.logs_state.tick();
         self.source_state.tick();
         self.map_state.tick();
-        self.countdown_state.tick();
+        self.globe_state.tick();
+        self.sensors_state.tick();
+        // The countdown panel rotates through the timers unless one is being picked
+        self.timers_state.rotating = !matches!(self.focus, Some(Panel::Countdown | Panel::Timers));
+        self.timers_state.tick();
+        self.clock_state.rotating = !matches!(self.focus, Some(Panel::Clock | Panel::Analog));
+        self.clock_state.follow_node(self.map_state.selected_node());
+        for index in self.timers_state.take_expired() {
+            self.run_expiry_actions(index);
+        }
         self.clock_state.tick();
         self.hex_state.tick();
         self.progress_state.tick();
+        self.self_destruct.tick();
+
+        let due = self.scenario.as_mut().map(Scenario::tick).unwrap_or_default();
+        for index in due {
+            self.run_cue(index);
+        }
+    }
+
+    /// Act on the named timer, or the selected one if unnamed
+    fn timer_command(&mut self, name: Option<String>, action: fn(&mut CountdownState)) {
+        match self.timers_state.pin(name.as_deref()) {
+            Some(timer) => action(timer),
+            None => self.logs_state.push(LogLevel::Warn, format!("Unknown timer '{}'", name.unwrap_or_default())),
+        }
+    }
+
+    /// Run the commands of a scenario cue
+    fn run_cue(&mut self, index: usize) {
+        let Some(scenario) = &self.scenario else {
+            return;
+        };
+        for command in scenario.cues[index].commands.clone() {
+            self.run_command(command);
+        }
+    }
+
+    /// Hand a new system snapshot to the monitors that use its parts
+    fn apply_stats(&mut self, stats: SystemStats) {
+        self.system_stats = stats;
+        let stats = &self.system_stats;
+        let sampled = stats.sampled;
+
+        if sampled.cpu {
+            self.cpu_state.update(stats);
+        }
+        if sampled.memory {
+            self.memory_state.update(stats);
+        }
+        if sampled.network {
+            self.network_state.update(stats);
+        }
+        if sampled.sensors {
+            self.sensors_state.update(stats);
+        }
+        if sampled.disks {
+            self.disk_state.update(stats);
+        }
+        if sampled.processes {
+            self.process_state.update(stats);
+        }
+
+        let alerts = self
+            .sensors_state
+            .take_alerts()
+            .into_iter()
+            .chain(self.disk_state.take_alerts())
+            .chain(self.process_state.take_announcements());
+        for alert in alerts {
+            self.logs_state.push(LogLevel::Alert, alert);
+        }
+
+        let events = self.alerts.evaluate(&self.system_stats);
+        for event in events {
+            self.handle_alert_event(event);
+        }
+    }
+
+    /// Log an alert rule firing or recovering, and show its banner or run its command
+    fn handle_alert_event(&mut self, event: AlertEvent) {
+        match event {
+            AlertEvent::Fired(index, message) => {
+                let rule = self.alerts.rule(index);
+                let (name, banner, command) = (rule.name.clone(), rule.banner, rule.command.clone());
+                self.logs_state.push(LogLevel::Alert, format!("ALERT {name}: {message}"));
+                if banner {
+                    self.banner = Some(Banner { title: name.clone(), message: message.clone(), rule: Some(index) });
+                }
+                if let Some(command) = command {
+                    let env = [("ALERT_NAME", name.as_str()), ("ALERT_MESSAGE", message.as_str())];
+                    if let Err(err) = spawn_shell(&command, &env) {
+                        self.logs_state.push(LogLevel::Warn, format!("Alert command failed: {err}"));
+                    }
+                }
+            }
+            AlertEvent::Recovered(index, message) => {
+                let name = &self.alerts.rule(index).name;
+                self.logs_state.push(LogLevel::Success, format!("RECOVERED {name}: {message}"));
+                if self.banner.as_ref().is_some_and(|banner| banner.rule == Some(index)) {
+                    self.banner = None;
+                }
+            }
+        }
+    }
+
+    /// Run the configured actions of a timer that reached zero
+    fn run_expiry_actions(&mut self, index: usize) {
+        let timer = &self.timers_state.timers[index];
+        let name = timer.title().to_string();
+        let event = if timer.mode == CountdownMode::Mission { "LIFTOFF" } else { "COUNTDOWN EXPIRED" };
+        let expiry = timer.expiry.clone();
+
+        if expiry.alert {
+            self.logs_state.push(LogLevel::Alert, format!("{event}: {name}"));
+        }
+        if expiry.bell {
+            self.bell = true;
+        }
+        if expiry.effect {
+            self.self_destruct.start();
+        }
+        if let Some(command) = &expiry.command {
+            if let Err(err) = spawn_shell(command, &[("TIMER_NAME", name.as_str())]) {
+                self.logs_state.push(LogLevel::Warn, format!("Expiry command failed: {err}"));
+            }
+        }
+        if let Some(layout) = expiry.layout {
+            self.run_command(Command::Layout(layout));
+        }
     }
 
     /// Handle keyboard input
@@ -106,7 +389,46 @@ impl App {
             return;
         }
 
+        // Any key dismisses an alert banner or the self destruct effect
+        if self.banner.take().is_some() {
+            return;
+        }
+        if self.self_destruct.is_active() {
+            self.self_destruct.stop();
+            return;
+        }
+
+        // The command prompt captures all keys while open
+        if self.command_input.is_some() {
+            self.handle_command_key(key);
+            return;
+        }
+
+        // Keys for the focused panel come first
+        if self.focus == Some(Panel::Map) && self.handle_map_key(key) {
+            return;
+        }
+        if self.focus == Some(Panel::Network) && self.handle_network_key(key) {
+            return;
+        }
+        if self.focus == Some(Panel::Processes) && self.handle_process_key(key) {
+            return;
+        }
+        if matches!(self.focus, Some(Panel::Countdown | Panel::Timers)) && self.handle_timer_key(key) {
+            return;
+        }
+        if matches!(self.focus, Some(Panel::Clock | Panel::Analog)) && self.handle_clock_key(key) {
+            return;
+        }
+
         match key.code {
+            // Leave zoom, then focus, then quit
+            KeyCode::Esc if self.zoomed => {
+                self.zoomed = false;
+            }
+            KeyCode::Esc if self.focus.is_some() => {
+                self.focus = None;
+            }
             // Quit
             KeyCode::Char('q') | KeyCode::Esc => {
                 self.running = false;
@@ -118,6 +440,12 @@ impl App {
             // Pause/Resume
             KeyCode::Char(' ') => {
                 self.paused = !self.paused;
+                let now = self.time.now();
+                if self.paused {
+                    self.timers_state.pause(now);
+                } else {
+                    self.timers_state.unpause(now);
+                }
             }
             // Speed controls
             KeyCode::Char('+') | KeyCode::Char('=') => {
@@ -126,9 +454,46 @@ impl App {
             KeyCode::Char('-') => {
                 self.animation_speed = (self.animation_speed - 0.25).max(0.25);
             }
-            // Reset countdown
+            // Reset the selected countdown
             KeyCode::Char('r') => {
-                self.countdown_state.reset();
+                self.timers_state.pinned_mut().reset();
+            }
+            // Hold or resume the selected countdown
+            KeyCode::Char('H') => {
+                self.timers_state.pinned_mut().toggle_hold();
+            }
+            // Jump to the next or previous scenario cue
+            KeyCode::Char(']') => {
+                if let Some(index) = self.scenario.as_mut().and_then(Scenario::next_cue) {
+                    self.run_cue(index);
+                }
+            }
+            KeyCode::Char('[') => {
+                if let Some(index) = self.scenario.as_mut().and_then(Scenario::previous_cue) {
+                    self.run_cue(index);
+                }
+            }
+            // Reveal the configured matrix message
+            KeyCode::Char('m') => {
+                self.run_command(Command::Reveal(None));
+            }
+            // Command prompt
+            KeyCode::Char(':') => {
+                self.command_input = Some(String::new());
+            }
+            // Panel focus and zoom
+            KeyCode::Tab => {
+                self.cycle_focus(true);
+            }
+            KeyCode::BackTab => {
+                self.cycle_focus(false);
+            }
+            KeyCode::Enter if self.focus.is_some() => {
+                self.zoomed = !self.zoomed;
+            }
+            // Next dashboard layout
+            KeyCode::Char('L') => {
+                self.set_layout((self.layout + 1) % self.layouts.len());
             }
             // Help
             KeyCode::Char('?') | KeyCode::Char('h') => {
@@ -138,6 +503,275 @@ impl App {
         }
     }
 
+    /// Pan, zoom and region keys while the map is focused
+    fn handle_map_key(&mut self, key: KeyEvent) -> bool {
+        const PAN_STEP: f64 = 0.1;
+        const ZOOM_STEP: f64 = 1.5;
+        const GLOBE_STEP: f64 = 10.0;
+        let map = &mut self.map_state;
+
+        match key.code {
+            KeyCode::Char('g') => {
+                map.toggle_projection();
+                return true;
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                map.select_node(key.code == KeyCode::Char('n'));
+                return true;
+            }
+            _ => {}
+        }
+
+        // On the globe the arrows spin and tilt it instead
+        if map.projection == Projection::Globe {
+            let globe = &mut self.globe_state;
+            match key.code {
+                KeyCode::Left => globe.rotate(-GLOBE_STEP),
+                KeyCode::Right => globe.rotate(GLOBE_STEP),
+                KeyCode::Up => globe.tilt_by(GLOBE_STEP),
+                KeyCode::Down => globe.tilt_by(-GLOBE_STEP),
+                _ => return false,
+            }
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Left => map.pan(-PAN_STEP, 0.0),
+            KeyCode::Right => map.pan(PAN_STEP, 0.0),
+            KeyCode::Up => map.pan(0.0, PAN_STEP),
+            KeyCode::Down => map.pan(0.0, -PAN_STEP),
+            KeyCode::Char('i') => map.zoom(ZOOM_STEP, 0.5, 0.5),
+            KeyCode::Char('o') => map.zoom(1.0 / ZOOM_STEP, 0.5, 0.5),
+            KeyCode::Char('t') => map.toggle_tour(),
+            KeyCode::Char(c @ '0'..='9') => {
+                return map.select_region(c as usize - '0' as usize);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Timer selection and start/pause while a timer panel is focused
+    fn handle_timer_key(&mut self, key: KeyEvent) -> bool {
+        let timers = &mut self.timers_state;
+        match key.code {
+            KeyCode::Up | KeyCode::Left => timers.cycle(false),
+            KeyCode::Down | KeyCode::Right => timers.cycle(true),
+            KeyCode::Char('s') => timers.pinned_mut().toggle_hold(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Zone keys while the clock is focused
+    fn handle_clock_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Left => self.clock_state.cycle(false),
+            KeyCode::Right => self.clock_state.cycle(true),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Interface, scale and unit keys while the network monitor is focused
+    fn handle_network_key(&mut self, key: KeyEvent) -> bool {
+        let network = &mut self.network_state;
+        match key.code {
+            KeyCode::Left => network.cycle(false),
+            KeyCode::Right => network.cycle(true),
+            KeyCode::Char('s') => network.toggle_log_scale(),
+            KeyCode::Char('b') => network.toggle_unit(),
+            KeyCode::Char('u') => network.toggle_prefix(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Sort keys while the process table is focused
+    fn handle_process_key(&mut self, key: KeyEvent) -> bool {
+        let column = match key.code {
+            KeyCode::Char('p') => ProcessSort::Pid,
+            KeyCode::Char('n') => ProcessSort::Name,
+            KeyCode::Char('c') => ProcessSort::Cpu,
+            KeyCode::Char('m') => ProcessSort::Memory,
+            KeyCode::Char('u') => ProcessSort::User,
+            _ => return false,
+        };
+        self.process_state.sort_by(column);
+        true
+    }
+
+    /// Handle mouse input: click to focus, wheel to zoom and drag to pan the map
+    pub fn handle_mouse_event(&mut self, mouse: MouseEvent) {
+        let (width, height) = self.terminal_size;
+        let screen = Rect::new(0, 0, width, height);
+        let areas = panel_areas(screen, self.current_layout(), self.zoomed_panel());
+
+        // A drag follows the pointer anywhere on screen until the button is released
+        match mouse.kind {
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let map = areas.iter().find(|(panel, _)| *panel == Panel::Map);
+                if let (Some((column, row)), Some((_, rect))) = (self.drag_from, map) {
+                    let inner = rect.inner(Margin::new(1, 1));
+                    let dx = (column as f64 - mouse.column as f64) / inner.width.max(1) as f64;
+                    let dy = (mouse.row as f64 - row as f64) / inner.height.max(1) as f64;
+                    match self.map_state.projection {
+                        Projection::Flat => self.map_state.pan(dx, dy),
+                        Projection::Globe => {
+                            self.globe_state.rotate(dx * 180.0);
+                            self.globe_state.tilt_by(-dy * 90.0);
+                        }
+                    }
+                    self.drag_from = Some((mouse.column, mouse.row));
+                }
+                return;
+            }
+            MouseEventKind::Up(_) => {
+                self.drag_from = None;
+                return;
+            }
+            _ => {}
+        }
+
+        let hit = areas
+            .into_iter()
+            .find(|(_, rect)| rect.contains((mouse.column, mouse.row).into()));
+        let Some((panel, rect)) = hit else {
+            return;
+        };
+
+        // Position inside the map canvas as fractions of its size
+        let inner = rect.inner(Margin::new(1, 1));
+        let fraction = |value: u16, start: u16, size: u16| {
+            (value.saturating_sub(start) as f64 + 0.5) / size.max(1) as f64
+        };
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if panel.is_focusable() {
+                    self.focus = Some(panel);
+                }
+                self.drag_from = (panel == Panel::Map).then_some((mouse.column, mouse.row));
+            }
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                if panel == Panel::Map && self.map_state.projection == Projection::Flat =>
+            {
+                let factor = if mouse.kind == MouseEventKind::ScrollUp { 1.25 } else { 0.8 };
+                let fx = fraction(mouse.column, inner.x, inner.width);
+                let fy = fraction(mouse.row, inner.y, inner.height);
+                self.map_state.zoom(factor, fx.clamp(0.0, 1.0), fy.clamp(0.0, 1.0));
+            }
+            _ => {}
+        }
+    }
+
+    /// The panel shown full screen, if any
+    pub fn zoomed_panel(&self) -> Option<Panel> {
+        self.focus.filter(|_| self.zoomed)
+    }
+
+    pub fn current_layout(&self) -> &DashboardLayout {
+        &self.layouts[self.layout]
+    }
+
+    /// Move focus to the next or previous focusable panel of the layout
+    fn cycle_focus(&mut self, forward: bool) {
+        let panels: Vec<Panel> = self
+            .current_layout()
+            .panels()
+            .into_iter()
+            .filter(|panel| panel.is_focusable())
+            .collect();
+        if panels.is_empty() {
+            return;
+        }
+
+        let next = match self.focus.and_then(|focus| panels.iter().position(|&p| p == focus)) {
+            Some(index) if forward => (index + 1) % panels.len(),
+            Some(index) => (index + panels.len() - 1) % panels.len(),
+            None if forward => 0,
+            None => panels.len() - 1,
+        };
+        self.focus = Some(panels[next]);
+    }
+
+    /// Switch to another layout, dropping focus if its panel is gone
+    fn set_layout(&mut self, index: usize) {
+        self.layout = index;
+        if self.focus.is_some_and(|panel| !self.current_layout().contains(panel)) {
+            self.focus = None;
+            self.zoomed = false;
+        }
+        let name = self.current_layout().name.to_uppercase();
+        self.logs_state.push(LogLevel::Info, format!("Layout: {name}"));
+    }
+
+    /// Handle keyboard input while the command prompt is open
+    fn handle_command_key(&mut self, key: KeyEvent) {
+        let Some(input) = &mut self.command_input else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.command_input = None;
+            }
+            KeyCode::Enter => {
+                let line = std::mem::take(input);
+                self.command_input = None;
+                match Command::parse(&line) {
+                    Ok(command) => self.run_command(command),
+                    Err(err) => self.logs_state.push(LogLevel::Warn, format!("Command failed: {err}")),
+                }
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => {
+                input.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    /// Execute a command from the prompt or a key binding
+    pub fn run_command(&mut self, command: Command) {
+        match command {
+            Command::Reveal(message) => {
+                if !self.matrix_state.reveal(message.as_deref()) {
+                    self.logs_state.push(LogLevel::Warn, "No matrix message configured");
+                }
+            }
+            Command::Hold(name) => self.timer_command(name, CountdownState::hold),
+            Command::Resume(name) => self.timer_command(name, CountdownState::resume),
+            Command::Start(name) => self.timer_command(name, |timer| {
+                timer.reset();
+                timer.resume();
+            }),
+            Command::Log(level, message) => self.logs_state.push(level, message),
+            Command::Connect(from, to) => {
+                if let Err(err) = self.map_state.connect(&from, &to) {
+                    self.logs_state.push(LogLevel::Warn, format!("Command failed: {err}"));
+                }
+            }
+            Command::Banner(banner) => {
+                self.banner = banner.map(|(title, message)| Banner { title, message, rule: None });
+            }
+            Command::Theme(theme) => self.theme = theme,
+            Command::Progress(label, fraction) => {
+                if !self.progress_state.set(&label, fraction) {
+                    self.logs_state.push(LogLevel::Warn, format!("No free progress bar for '{label}'"));
+                }
+            }
+            Command::Layout(name) => {
+                match DashboardLayout::find(&self.layouts, &name) {
+                    Some(index) => self.set_layout(index),
+                    None => self.logs_state.push(LogLevel::Warn, format!("Unknown layout '{name}'")),
+                }
+            }
+        }
+    }
+
     /// Handle terminal resize
     pub fn handle_resize(&mut self, width: u16, height: u16) {
         self.terminal_size = (width, height);
@@ -145,8 +779,28 @@ impl App {
     }
 }
 
+/// A full-screen banner waiting for a key press
+pub struct Banner {
+    pub title: String,
+    pub message: String,
+    /// Alert rule that raised it, cleared when the rule recovers
+    pub rule: Option<usize>,
+}
+
+/// Parts of the system the given panels show
+fn subsystems_for(panels: &[Panel]) -> Subsystems {
+    Subsystems {
+        cpu: panels.contains(&Panel::Cpu),
+        memory: panels.contains(&Panel::Memory),
+        network: panels.contains(&Panel::Network),
+        sensors: panels.contains(&Panel::Sensors),
+        disks: panels.contains(&Panel::Disks),
+        processes: panels.contains(&Panel::Processes),
+    }
+}
+
 impl Default for App {
     fn default() -> Self {
-        Self::new()
+        Self::new(&Config::default()).expect("default config is valid")
     }
 }
@@ -0,0 +1,157 @@
+use crate::data::progress_feed::parse_line;
+use crate::ui::theme::Theme;
+use crate::widgets::fake_logs::LogLevel;
+
+/// Commands that can be typed at the `:` prompt or run by scenario cues
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Reveal a message in the matrix rain (configured message if empty)
+    Reveal(Option<String>),
+    /// Switch to a dashboard layout by name
+    Layout(String),
+    /// Stop a countdown (the selected one if unnamed) while the clock keeps going
+    Hold(Option<String>),
+    /// Carry on counting after a hold
+    Resume(Option<String>),
+    /// Start a countdown over from the top
+    Start(Option<String>),
+    /// Push a line to the logs panel
+    Log(LogLevel, String),
+    /// Open a connection between two map nodes, by name
+    Connect(String, String),
+    /// Show a full-screen banner with a title and message, or clear it
+    Banner(Option<(String, String)>),
+    /// Recolor the dashboard
+    Theme(Theme),
+    /// Set the progress of the bar with a label, taking a free bar if none has it
+    Progress(String, f64),
+}
+
+impl Command {
+    /// Parse a command line such as `reveal WAKE UP NEO`
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let (name, rest) = match input.split_once(char::is_whitespace) {
+            Some((name, rest)) => (name, rest.trim()),
+            None => (input, ""),
+        };
+
+        match name.to_ascii_lowercase().as_str() {
+            "reveal" => Ok(Command::Reveal(non_empty(rest))),
+            "layout" => non_empty(rest)
+                .map(Command::Layout)
+                .ok_or_else(|| "usage: layout NAME".to_string()),
+            "hold" | "stop" => Ok(Command::Hold(non_empty(rest))),
+            "resume" => Ok(Command::Resume(non_empty(rest))),
+            "start" => Ok(Command::Start(non_empty(rest))),
+            "log" => parse_log(rest),
+            "connect" => parse_connect(rest),
+            "banner" => Ok(Command::Banner(non_empty(rest).map(|text| match text.split_once(':') {
+                Some((title, message)) => (title.trim().to_string(), message.trim().to_string()),
+                None => (text, String::new()),
+            }))),
+            "theme" => Theme::from_name(rest)
+                .map(Command::Theme)
+                .ok_or_else(|| format!("unknown theme '{rest}' (neon, amber, green, red, blue or mono)")),
+            "progress" => match parse_line(rest) {
+                Some((Some(label), fraction)) => Ok(Command::Progress(label, fraction)),
+                _ => Err("usage: progress LABEL PERCENT".to_string()),
+            },
+            "" => Err("empty command".to_string()),
+            _ => Err(format!("unknown command '{name}'")),
+        }
+    }
+}
+
+/// `log [LEVEL] MESSAGE`, at info level unless the first word names one
+fn parse_log(rest: &str) -> Result<Command, String> {
+    let (first, message) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let level = match first.to_ascii_lowercase().as_str() {
+        "info" => Some(LogLevel::Info),
+        "warn" => Some(LogLevel::Warn),
+        "error" => Some(LogLevel::Error),
+        "success" => Some(LogLevel::Success),
+        "debug" => Some(LogLevel::Debug),
+        "alert" => Some(LogLevel::Alert),
+        _ => None,
+    };
+
+    match level {
+        Some(level) => non_empty(message.trim()).map(|message| Command::Log(level, message)),
+        None => non_empty(rest).map(|message| Command::Log(LogLevel::Info, message)),
+    }
+    .ok_or_else(|| "usage: log [LEVEL] MESSAGE".to_string())
+}
+
+/// `connect FROM TO`, or `connect FROM to TO` for names with spaces
+fn parse_connect(rest: &str) -> Result<Command, String> {
+    let usage = || "usage: connect FROM TO".to_string();
+    let (from, to) = match rest.split_once(" to ") {
+        Some(pair) => pair,
+        None => rest.split_once(char::is_whitespace).ok_or_else(usage)?,
+    };
+
+    match (non_empty(from.trim()), non_empty(to.trim())) {
+        (Some(from), Some(to)) => Ok(Command::Connect(from, to)),
+        _ => Err(usage()),
+    }
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        let some = |text: &str| Some(text.to_string());
+        let cases = [
+            ("reveal", Command::Reveal(None)),
+            ("  REVEAL  WAKE UP NEO ", Command::Reveal(some("WAKE UP NEO"))),
+            ("layout launch", Command::Layout("launch".to_string())),
+            ("stop", Command::Hold(None)),
+            ("hold T-MINUS", Command::Hold(some("T-MINUS"))),
+            ("resume T-MINUS", Command::Resume(some("T-MINUS"))),
+            ("start", Command::Start(None)),
+            ("log all systems go", Command::Log(LogLevel::Info, "all systems go".to_string())),
+            ("log ERROR  core breach", Command::Log(LogLevel::Error, "core breach".to_string())),
+            ("connect BER TYO", Command::Connect("BER".to_string(), "TYO".to_string())),
+            ("connect New York to Tokyo", Command::Connect("New York".to_string(), "Tokyo".to_string())),
+            ("banner", Command::Banner(None)),
+            ("banner BREACH", Command::Banner(Some(("BREACH".to_string(), String::new())))),
+            ("banner BREACH : sector 7", Command::Banner(Some(("BREACH".to_string(), "sector 7".to_string())))),
+            ("theme Amber", Command::Theme(Theme::Amber)),
+            ("progress DEPLOY EU 73.5%", Command::Progress("DEPLOY EU".to_string(), 0.735)),
+            ("progress UPLOAD 150", Command::Progress("UPLOAD".to_string(), 1.0)),
+        ];
+        for (text, command) in cases {
+            assert_eq!(Command::parse(text), Ok(command), "{text}");
+        }
+    }
+
+    #[test]
+    fn rejects_bad_commands() {
+        let cases = [
+            ("", "empty command"),
+            ("   ", "empty command"),
+            ("launch", "unknown command 'launch'"),
+            ("layout", "usage: layout NAME"),
+            ("log", "usage: log [LEVEL] MESSAGE"),
+            ("log warn", "usage: log [LEVEL] MESSAGE"),
+            ("connect BER", "usage: connect FROM TO"),
+            ("theme pink", "unknown theme 'pink' (neon, amber, green, red, blue or mono)"),
+            ("progress 50", "usage: progress LABEL PERCENT"),
+            ("progress UPLOAD half", "usage: progress LABEL PERCENT"),
+        ];
+        for (text, error) in cases {
+            assert_eq!(Command::parse(text), Err(error.to_string()), "{text}");
+        }
+    }
+}
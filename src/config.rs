@@ -0,0 +1,674 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+/// Command line arguments
+#[derive(Debug, Default)]
+pub struct Args {
+    /// Explicit config file path (`--config`)
+    pub config: Option<PathBuf>,
+    /// GeoIP city database enabling the live connection map (`--geoip`)
+    pub geoip: Option<PathBuf>,
+    /// Virtual start time for reproducible recordings (`--time`)
+    pub time: Option<String>,
+    /// Scenario file of scripted cues to play (`--scenario`)
+    pub scenario: Option<PathBuf>,
+}
+
+impl Args {
+    /// Parse arguments from the process command line
+    pub fn parse() -> io::Result<Self> {
+        let mut args = Self::default();
+        let mut iter = env::args().skip(1);
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "-c" | "--config" => {
+                    args.config = Some(PathBuf::from(expect_value(&arg, iter.next())?));
+                }
+                "--geoip" => {
+                    args.geoip = Some(PathBuf::from(expect_value(&arg, iter.next())?));
+                }
+                "--time" => {
+                    args.time = Some(expect_value(&arg, iter.next())?);
+                }
+                "--scenario" => {
+                    args.scenario = Some(PathBuf::from(expect_value(&arg, iter.next())?));
+                }
+                _ => return Err(invalid_input(format!("unknown argument '{arg}'"))),
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+fn expect_value(flag: &str, value: Option<String>) -> io::Result<String> {
+    value.ok_or_else(|| invalid_input(format!("{flag} requires a value")))
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+/// Application configuration, read from a TOML file
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub matrix: MatrixConfig,
+    pub map: MapConfig,
+    pub globe: GlobeConfig,
+    pub network: NetworkConfig,
+    pub disks: DiskConfig,
+    pub processes: ProcessConfig,
+    pub sampling: SamplingConfig,
+    /// A single countdown; the first timer when `timers` are configured too
+    pub countdown: Option<CountdownConfig>,
+    /// Named countdown timers
+    pub timers: Vec<CountdownConfig>,
+    pub clock: ClockConfig,
+    pub font: FontConfig,
+    pub progress: ProgressConfig,
+    /// Threshold rules over the real system metrics
+    pub alerts: Vec<AlertRuleConfig>,
+    pub time: TimeConfig,
+    /// Layout shown at startup
+    pub layout: Option<String>,
+    /// Extra layouts, or replacements for bundled ones with the same name
+    pub layouts: Vec<LayoutConfig>,
+    /// Scenario file of scripted cues to play
+    pub scenario: Option<PathBuf>,
+}
+
+/// A file of layout definitions, as in the bundled `layouts.toml`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LayoutFile {
+    pub layouts: Vec<LayoutConfig>,
+}
+
+/// A scenario: cues run in order, each after a delay or on a key press
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioFile {
+    pub cues: Vec<CueConfig>,
+}
+
+/// One step of a scenario
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CueConfig {
+    /// Names the cue in load errors
+    pub name: Option<String>,
+    /// Seconds after the previous cue (or the start)
+    #[serde(default)]
+    pub after: f64,
+    /// Wait for the next-cue key instead of a delay
+    #[serde(default)]
+    pub wait: bool,
+    /// Commands to run, as typed at the `:` prompt
+    pub run: Vec<String>,
+}
+
+/// A named dashboard layout
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LayoutConfig {
+    pub name: String,
+    pub rows: Vec<RowConfig>,
+}
+
+/// A layout row: its height and the panels in each column
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RowConfig {
+    /// Lines ("5") or percentage ("30%"); remaining space if unset
+    pub height: Option<String>,
+    /// Width of each column; equal shares if empty
+    #[serde(default)]
+    pub widths: Vec<String>,
+    /// Panels of each column, top to bottom, e.g. `["cpu:4", "logs"]`
+    pub columns: Vec<Vec<String>>,
+}
+
+/// Matrix rain settings
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MatrixConfig {
+    /// Hidden message revealed in the rain
+    pub message: Option<String>,
+    /// Seconds for the drops to lock in the whole message
+    pub reveal_secs: f32,
+    /// Seconds the message stays readable
+    pub hold_secs: f32,
+    /// Seconds for the message to dissolve back into the rain
+    pub dissolve_secs: f32,
+    /// Seconds between automatic reveals (0 = only on demand)
+    pub interval_secs: f32,
+}
+
+impl Default for MatrixConfig {
+    fn default() -> Self {
+        Self {
+            message: None,
+            reveal_secs: 4.0,
+            hold_secs: 6.0,
+            dissolve_secs: 3.0,
+            interval_secs: 0.0,
+        }
+    }
+}
+
+/// World map settings
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MapConfig {
+    /// CSV node catalogue (bundled world cities if unset)
+    pub catalogue: Option<PathBuf>,
+    /// Only use nodes from these groups (empty = all groups)
+    pub groups: Vec<String>,
+    /// Maximum number of nodes, most important first (0 = no limit)
+    pub max_nodes: usize,
+    /// MaxMind `.mmdb` city database; enables the live connection map
+    pub geoip: Option<PathBuf>,
+    /// Catalogue label of the node live connections start from
+    pub home: Option<String>,
+    /// Region shown at startup, e.g. "europe" (whole world if unset)
+    pub viewport: Option<String>,
+    /// Extra named regions, added after the built-in ones
+    pub regions: Vec<RegionConfig>,
+    /// Start the auto-tour between regions with active connections
+    pub tour: bool,
+    /// Seconds spent on each region during the auto-tour
+    pub tour_secs: f32,
+    /// Show each node's local time next to its label
+    pub local_times: bool,
+    /// Shade the night side of the Earth
+    pub night: bool,
+    /// Show the network on the flat map or on the globe
+    pub projection: Projection,
+}
+
+/// How the world map panel draws the Earth
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Projection {
+    #[default]
+    Flat,
+    Globe,
+}
+
+/// A named map region, given by its longitude and latitude ranges
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegionConfig {
+    pub name: String,
+    /// West and east edge in degrees
+    pub lon: [f64; 2],
+    /// South and north edge in degrees
+    pub lat: [f64; 2],
+}
+
+impl Default for MapConfig {
+    fn default() -> Self {
+        Self {
+            catalogue: None,
+            groups: Vec::new(),
+            max_nodes: 16,
+            geoip: None,
+            home: None,
+            viewport: None,
+            regions: Vec::new(),
+            tour: false,
+            tour_secs: 10.0,
+            local_times: true,
+            night: true,
+            projection: Projection::Flat,
+        }
+    }
+}
+
+/// Rotating globe settings
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GlobeConfig {
+    /// Rotation speed in degrees of longitude per second
+    pub speed: f64,
+    /// Latitude facing the viewer, in degrees
+    pub tilt: f64,
+}
+
+impl Default for GlobeConfig {
+    fn default() -> Self {
+        Self {
+            speed: 6.0,
+            tilt: 20.0,
+        }
+    }
+}
+
+/// An alert rule such as `cpu > 90% for 10s`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertRuleConfig {
+    /// Condition: metric, comparison, threshold and an optional duration
+    pub rule: String,
+    /// Name in the logs and on the banner (the rule itself if unset)
+    pub name: Option<String>,
+    /// Show a full-screen banner when the rule fires
+    #[serde(default)]
+    pub banner: bool,
+    /// Shell command to run when the rule fires
+    pub command: Option<String>,
+}
+
+/// System statistics sampling settings
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SamplingConfig {
+    /// Seconds between samples of CPU, memory, network and the other monitors
+    pub interval_secs: f64,
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        Self { interval_secs: 1.0 }
+    }
+}
+
+/// Network monitor settings
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkConfig {
+    /// Interfaces to show, as patterns such as "eth*" (empty = all)
+    pub include: Vec<String>,
+    /// Interfaces to leave out, checked after `include`
+    pub exclude: Vec<String>,
+    /// Show rates in bytes or bits
+    pub units: RateUnit,
+    /// Binary (KiB, 1024) or decimal (kB, 1000) prefixes
+    pub prefix: UnitPrefix,
+    /// Logarithmic sparklines, so small traffic stays visible next to bursts
+    pub log_scale: bool,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: ["lo", "docker*", "veth*", "br-*"].map(String::from).to_vec(),
+            units: RateUnit::Bytes,
+            prefix: UnitPrefix::Iec,
+            log_scale: false,
+        }
+    }
+}
+
+/// Storage monitor settings
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiskConfig {
+    /// Mount points to show, e.g. "/" or "/home" (empty = all)
+    pub mounts: Vec<String>,
+    /// Log an alert when a filesystem gets fuller than this percentage (0 = never)
+    pub alert_percent: f64,
+}
+
+impl Default for DiskConfig {
+    fn default() -> Self {
+        Self {
+            mounts: Vec::new(),
+            alert_percent: 90.0,
+        }
+    }
+}
+
+/// Process table settings
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProcessConfig {
+    /// Column the table is sorted by at startup
+    pub sort: ProcessSort,
+    /// Announce every new process in the logs panel
+    pub dramatic: bool,
+}
+
+/// Process table column to sort by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessSort {
+    Pid,
+    Name,
+    #[default]
+    Cpu,
+    Memory,
+    User,
+}
+
+/// Unit for data amounts and rates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RateUnit {
+    #[default]
+    Bytes,
+    Bits,
+}
+
+/// Unit prefixes: powers of 1024 (IEC) or of 1000 (SI)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitPrefix {
+    #[default]
+    Iec,
+    Si,
+}
+
+/// How the countdown panel counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CountdownMode {
+    /// Count down to zero and expire
+    #[default]
+    Down,
+    /// Count up like a stopwatch
+    Up,
+    /// T-minus down to zero, then T-plus up from it
+    Mission,
+}
+
+/// Accent colors from the neon palette
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccentColor {
+    #[default]
+    Cyan,
+    Magenta,
+    Green,
+    Orange,
+    Purple,
+    Yellow,
+    Red,
+}
+
+/// A countdown timer
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CountdownConfig {
+    /// Shown as the panel title, e.g. "REACTOR CORE"
+    pub name: Option<String>,
+    pub mode: CountdownMode,
+    /// Length of the count when no target is set
+    pub seconds: u64,
+    /// Time to count to (or up from): `2026-12-31 23:59:59`, `23:00` or RFC 3339
+    pub target: Option<String>,
+    /// Time zone of a target without an offset; the local one if unset
+    pub timezone: Option<String>,
+    /// Show tenths of a second
+    pub tenths: bool,
+    /// Seconds left when the timer turns to warning
+    pub warning: u64,
+    /// Seconds left when the timer turns critical
+    pub critical: u64,
+    pub color: AccentColor,
+    /// Start counting right away; otherwise wait for a start key
+    pub autostart: bool,
+    /// What happens when the count reaches zero
+    pub expiry: ExpiryConfig,
+}
+
+/// Actions when a countdown reaches zero
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExpiryConfig {
+    /// Ring the terminal bell
+    pub bell: bool,
+    /// Play the full-screen self destruct effect
+    pub effect: bool,
+    /// Push an alert line to the logs panel
+    pub alert: bool,
+    /// Switch to this layout
+    pub layout: Option<String>,
+    /// Shell command to run
+    pub command: Option<String>,
+}
+
+impl Default for ExpiryConfig {
+    fn default() -> Self {
+        Self {
+            bell: false,
+            effect: false,
+            alert: true,
+            layout: None,
+            command: None,
+        }
+    }
+}
+
+impl Default for CountdownConfig {
+    fn default() -> Self {
+        Self {
+            name: None,
+            mode: CountdownMode::default(),
+            seconds: 300,
+            target: None,
+            timezone: None,
+            tenths: false,
+            warning: 60,
+            critical: 10,
+            color: AccentColor::default(),
+            autostart: true,
+            expiry: ExpiryConfig::default(),
+        }
+    }
+}
+
+/// How the clock panel shows several time zones
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClockShow {
+    /// One zone at a time, in turn
+    #[default]
+    Cycle,
+    /// All zones side by side
+    All,
+}
+
+/// A time zone on the clock
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ZoneConfig {
+    /// Shown above the time; the zone or node name if unset
+    pub label: Option<String>,
+    /// IANA time zone such as "Asia/Tokyo", "UTC" or "local"
+    pub zone: Option<String>,
+    /// Use the time zone of this world map node instead
+    pub node: Option<String>,
+}
+
+/// Clock panel settings
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClockConfig {
+    /// Show the time in big digits when the panel has room
+    pub big_digits: bool,
+    /// Time zones to show; the local one if empty
+    pub zones: Vec<ZoneConfig>,
+    pub show: ClockShow,
+    /// Seconds each zone is shown when cycling
+    pub cycle_secs: f64,
+    /// 12-hour time with AM/PM
+    pub hour12: bool,
+    /// strftime format for the time, e.g. "%H:%M"; overrides `hour12`
+    pub format: Option<String>,
+    /// Show the time at the node selected on the world map
+    pub follow_map: bool,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            big_digits: false,
+            zones: Vec::new(),
+            show: ClockShow::default(),
+            cycle_secs: 5.0,
+            hour12: false,
+            format: None,
+            follow_map: false,
+        }
+    }
+}
+
+/// Big digit fonts for the countdown and clock
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FontConfig {
+    /// "auto" for the biggest font that fits, or a font name
+    pub name: String,
+    /// FIGlet `.flf` fonts to add, named after the file
+    pub figlet: Vec<PathBuf>,
+}
+
+impl Default for FontConfig {
+    fn default() -> Self {
+        Self {
+            name: "auto".to_string(),
+            figlet: Vec::new(),
+        }
+    }
+}
+
+/// Operations panel settings
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProgressConfig {
+    /// Number of bars
+    pub count: usize,
+    /// Look of bars without their own style
+    pub style: ProgressStyle,
+    /// Bars bound to real progress; random operations fill the remaining slots
+    pub bars: Vec<ProgressBarConfig>,
+}
+
+impl Default for ProgressConfig {
+    fn default() -> Self {
+        Self {
+            count: 4,
+            style: ProgressStyle::default(),
+            bars: Vec::new(),
+        }
+    }
+}
+
+/// How a progress bar is drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressStyle {
+    /// Solid gauge with the percentage on it
+    #[default]
+    Gauge,
+    /// Separate lit segments
+    Blocks,
+    /// Braille dots, two steps per cell
+    Braille,
+    /// Spinner and percentage beside a thin line
+    Spinner,
+    /// Ring filling clockwise
+    Radial,
+    /// Packets travelling along the filled part
+    Packets,
+}
+
+/// A bar following one source: `file` with `size`, `pipe`, `command` or
+/// `seconds`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProgressBarConfig {
+    /// Shown on the bar; derived from the source if unset
+    pub label: Option<String>,
+    pub color: Option<AccentColor>,
+    /// Look of the bar (the panel's style if unset)
+    pub style: Option<ProgressStyle>,
+    /// Named stages splitting the bar evenly
+    #[serde(default)]
+    pub stages: Vec<String>,
+    /// File growing toward `size`
+    pub file: Option<PathBuf>,
+    /// Final size of `file`, such as "250MB" or "1.5GiB"
+    pub size: Option<String>,
+    /// Named pipe, or "-" for stdin, sending `label percent` lines
+    pub pipe: Option<PathBuf>,
+    /// Shell command started with the app, followed through `pattern`
+    pub command: Option<String>,
+    /// Regex on the command's output: one group for a percentage, or two
+    /// for done and total (default: a number before "%")
+    pub pattern: Option<String>,
+    /// Fill over this many seconds
+    pub seconds: Option<f64>,
+}
+
+/// Time settings
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimeConfig {
+    /// Start of the virtual time; the real time is used if unset
+    pub start: Option<String>,
+    /// Virtual seconds per real second
+    pub speed: f64,
+}
+
+impl Default for TimeConfig {
+    fn default() -> Self {
+        Self {
+            start: None,
+            speed: 1.0,
+        }
+    }
+}
+
+impl Config {
+    /// Load the config from `--config`, or from the default location if it exists
+    pub fn load(args: &Args) -> io::Result<Self> {
+        let mut config = match &args.config {
+            Some(path) => Self::from_file(path)?,
+            None => match default_path() {
+                Some(path) if path.exists() => Self::from_file(&path)?,
+                _ => Self::default(),
+            },
+        };
+
+        // Command line options win over the file
+        if let Some(geoip) = &args.geoip {
+            config.map.geoip = Some(geoip.clone());
+        }
+        if let Some(time) = &args.time {
+            config.time.start = Some(time.clone());
+        }
+        if let Some(scenario) = &args.scenario {
+            config.scenario = Some(scenario.clone());
+        }
+
+        Ok(config)
+    }
+
+    fn from_file(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path).map_err(|e| {
+            io::Error::new(e.kind(), format!("{}: {e}", path.display()))
+        })?;
+
+        toml::from_str(&text).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display()))
+        })
+    }
+}
+
+/// `$XDG_CONFIG_HOME/future/config.toml`, falling back to `~/.config`
+fn default_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(base.join("future").join("config.toml"))
+}
@@ -0,0 +1,243 @@
+use chrono_tz::Tz;
+use std::{fs, io, path::Path};
+
+use crate::config::MapConfig;
+
+/// Bundled dataset of world cities
+const BUNDLED_CITIES: &str = include_str!("../../assets/cities.csv");
+/// Mean Earth radius in kilometres
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// A city or facility that can appear as a node on the world map
+#[derive(Clone, Debug)]
+pub struct CatalogueEntry {
+    /// Full name, e.g. "San Francisco"
+    pub name: String,
+    /// Short label drawn on the map, e.g. "SFO"
+    pub label: String,
+    pub lat: f64,
+    pub lon: f64,
+    /// Initial state on the map
+    pub active: bool,
+    /// Free-form group, e.g. a region or a production's facility type
+    pub group: String,
+    /// Weight used to pick nodes and random connections
+    pub importance: f64,
+    /// IANA time zone for the node's local time
+    pub tz: Option<Tz>,
+}
+
+/// Node catalogue loaded from a CSV file with the columns
+/// `name,label,lat,lon,state,group,importance` and an optional `tz`
+#[derive(Clone, Debug)]
+pub struct Catalogue {
+    pub entries: Vec<CatalogueEntry>,
+}
+
+impl Catalogue {
+    /// The bundled world city dataset
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_CITIES).expect("bundled city catalogue is valid")
+    }
+
+    /// The configured catalogue, or the bundled one
+    pub fn load(config: &MapConfig) -> io::Result<Self> {
+        match &config.catalogue {
+            Some(path) => Self::from_file(path),
+            None => Ok(Self::bundled()),
+        }
+    }
+
+    /// Load a catalogue from a CSV file
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+
+        Self::parse(&text).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display()))
+        })
+    }
+
+    /// Parse catalogue CSV; blank lines, `#` comments and a header in the
+    /// first row are skipped. Fields are split on every comma: quoting is
+    /// not supported, so names cannot contain commas.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut entries = Vec::new();
+        let mut first_row = true;
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if std::mem::take(&mut first_row) && is_header(line) {
+                continue;
+            }
+
+            let entry = parse_entry(line).map_err(|e| format!("line {}: {e}", number + 1))?;
+            entries.push(entry);
+        }
+
+        if entries.is_empty() {
+            return Err("catalogue has no entries".to_string());
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Pick the most important entries, optionally restricted to some groups.
+    /// Entries of equal importance keep their catalogue order.
+    pub fn select(&self, groups: &[String], max_nodes: usize) -> Vec<CatalogueEntry> {
+        let mut selected: Vec<CatalogueEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| groups.is_empty() || groups.iter().any(|g| g.eq_ignore_ascii_case(&entry.group)))
+            .cloned()
+            .collect();
+
+        selected.sort_by(|a, b| b.importance.total_cmp(&a.importance));
+        if max_nodes > 0 {
+            selected.truncate(max_nodes);
+        }
+        selected
+    }
+
+    /// Find an entry by its map label (case-insensitive)
+    pub fn find(&self, label: &str) -> Option<&CatalogueEntry> {
+        self.entries.iter().find(|entry| entry.label.eq_ignore_ascii_case(label))
+    }
+
+    /// The entry closest to a position, if within `max_km`
+    pub fn nearest(&self, lat: f64, lon: f64, max_km: f64) -> Option<&CatalogueEntry> {
+        self.entries
+            .iter()
+            .map(|entry| (entry, distance_km(lat, lon, entry.lat, entry.lon)))
+            .filter(|&(_, km)| km <= max_km)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entry, _)| entry)
+    }
+}
+
+/// Great-circle distance between two positions (haversine formula)
+fn distance_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let dphi = (lat2 - lat1).to_radians();
+    let dlambda = (lon2 - lon1).to_radians();
+
+    let a = (dphi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (dlambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Column names of the catalogue, the time zone being optional
+const COLUMNS: [&str; 8] = ["name", "label", "lat", "lon", "state", "group", "importance", "tz"];
+
+/// Is the row the column names, with or without the time zone?
+fn is_header(line: &str) -> bool {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    (fields.len() == 7 || fields.len() == 8)
+        && fields.iter().zip(COLUMNS).all(|(field, column)| field.eq_ignore_ascii_case(column))
+}
+
+fn parse_entry(line: &str) -> Result<CatalogueEntry, String> {
+    if line.contains('"') {
+        return Err("quoted fields are not supported".to_string());
+    }
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    // The time zone column is optional
+    let (columns, tz) = match fields.len() {
+        8 => (&fields[..7], fields[7]),
+        _ => (&fields[..], ""),
+    };
+    let [name, label, lat, lon, state, group, importance] = columns[..] else {
+        return Err(format!("expected 7 or 8 fields, found {}", fields.len()));
+    };
+
+    let number = |field: &str, value: &str| {
+        value
+            .parse::<f64>()
+            .map_err(|_| format!("invalid {field} '{value}'"))
+    };
+
+    let lat = number("lat", lat)?;
+    let lon = number("lon", lon)?;
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+        return Err(format!("coordinates out of range: {lat}, {lon}"));
+    }
+
+    let active = match state.to_ascii_lowercase().as_str() {
+        "active" => true,
+        "idle" => false,
+        _ => return Err(format!("invalid state '{state}' (expected active or idle)")),
+    };
+
+    Ok(CatalogueEntry {
+        name: name.to_string(),
+        label: label.to_string(),
+        lat,
+        lon,
+        active,
+        group: group.to_string(),
+        importance: number("importance", importance)?.max(0.0),
+        tz: match tz {
+            "" => None,
+            tz => Some(tz.parse().map_err(|_| format!("unknown time zone '{tz}'"))?),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_entries_with_and_without_a_time_zone() {
+        let catalogue = Catalogue::parse(
+            "# facilities\n\
+             name,label,lat,lon,state,group,importance,tz\n\
+             \n\
+             Tyrell HQ, TYR, 34.05, -118.24, active, hq, 10, America/Los_Angeles\n\
+             Off-world Relay,OWR,-20.0,120.0,idle,relay,-3\n",
+        )
+        .unwrap();
+
+        let [hq, relay] = &catalogue.entries[..] else {
+            panic!("expected two entries");
+        };
+        assert_eq!((hq.name.as_str(), hq.label.as_str()), ("Tyrell HQ", "TYR"));
+        assert_eq!((hq.lat, hq.lon), (34.05, -118.24));
+        assert!(hq.active);
+        assert_eq!(hq.tz, Some(chrono_tz::America::Los_Angeles));
+        assert!(!relay.active);
+        assert_eq!(relay.group, "relay");
+        assert_eq!(relay.importance, 0.0);
+        assert_eq!(relay.tz, None);
+    }
+
+    #[test]
+    fn header_is_only_skipped_in_the_first_row() {
+        let text = "A,AA,1,2,active,x,5\nname,label,lat,lon,state,group,importance\n";
+        assert_eq!(Catalogue::parse(text).unwrap_err(), "line 2: invalid lat 'lat'");
+        assert!(Catalogue::parse("NAME,Label,lat,lon,state,group,importance\nA,AA,1,2,active,x,5").is_ok());
+    }
+
+    #[test]
+    fn rejects_bad_rows_with_their_line_number() {
+        let cases = [
+            ("A,AA,1,2,active,x", "line 1: expected 7 or 8 fields, found 6"),
+            ("# c\nA,AA,1,2,active,x,5,UTC,extra", "line 2: expected 7 or 8 fields, found 9"),
+            ("\"Washington, DC\",WAS,38.9,-77.0,active,x,5", "line 1: quoted fields are not supported"),
+            ("A,AA,91,2,active,x,5", "line 1: coordinates out of range: 91, 2"),
+            ("A,AA,1,2,busy,x,5", "line 1: invalid state 'busy' (expected active or idle)"),
+            ("A,AA,1,2,idle,x,5,Mars/Olympus", "line 1: unknown time zone 'Mars/Olympus'"),
+            ("name,label,lat,lon,state,group,importance\n# only a header", "catalogue has no entries"),
+        ];
+        for (text, error) in cases {
+            assert_eq!(Catalogue::parse(text).unwrap_err(), error, "{text}");
+        }
+    }
+
+    #[test]
+    fn bundled_catalogue_parses() {
+        assert!(!Catalogue::bundled().entries.is_empty());
+    }
+}
@@ -0,0 +1,14 @@
+/// Bundled coastline sample points
+const BUNDLED_COASTLINE: &str = include_str!("../../assets/coastline.csv");
+
+/// World coastline sample points as (lon, lat)
+pub fn coastline() -> Vec<(f64, f64)> {
+    BUNDLED_COASTLINE
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let (lon, lat) = line.split_once(',')?;
+            Some((lon.trim().parse().ok()?, lat.trim().parse().ok()?))
+        })
+        .collect()
+}
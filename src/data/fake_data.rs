@@ -19,6 +19,7 @@ pub fn random_port() -> u16 {
 }
 
 /// Generate a random hex string
+#[allow(dead_code)]
 pub fn random_hex(length: usize) -> String {
     let mut rng = rand::thread_rng();
     (0..length)
@@ -28,6 +29,7 @@ pub fn random_hex(length: usize) -> String {
 }
 
 /// Generate a random percentage
+#[allow(dead_code)]
 pub fn random_percentage() -> f64 {
     rand::thread_rng().gen_range(0.0..100.0)
 }
@@ -73,16 +75,6 @@ pub fn random_username() -> &'static str {
     USERS[rng.gen_range(0..USERS.len())]
 }
 
-/// Generate fake city names for the world map
-pub fn random_city() -> &'static str {
-    const CITIES: &[&str] = &[
-        "New York", "London", "Tokyo", "Berlin", "Sydney",
-        "Moscow", "Shanghai", "Paris", "Dubai", "Singapore",
-    ];
-    let mut rng = rand::thread_rng();
-    CITIES[rng.gen_range(0..CITIES.len())]
-}
-
 /// Generate dramatic hacker-style messages
 pub fn dramatic_message() -> &'static str {
     const MESSAGES: &[&str] = &[
@@ -0,0 +1,268 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    path::Path,
+};
+
+use chrono_tz::Tz;
+use maxminddb::{geoip2, Reader};
+
+use crate::data::catalogue::Catalogue;
+
+/// TCP state code for ESTABLISHED in `/proc/net/tcp`
+const TCP_ESTABLISHED: &str = "01";
+/// Remote locations this close to a catalogue city are labelled with it
+const NEAREST_CITY_KM: f64 = 150.0;
+/// Seconds between samples of the established connections
+pub const LIVE_INTERVAL_SECS: f64 = 2.0;
+
+/// A geolocated position from the GeoIP database
+pub struct GeoLocation {
+    pub lat: f64,
+    pub lon: f64,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub tz: Option<Tz>,
+}
+
+/// Offline GeoIP lookups in a MaxMind-format `.mmdb` city database
+pub struct GeoIp {
+    reader: Reader<Vec<u8>>,
+}
+
+impl GeoIp {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let reader = Reader::open_readfile(path).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display()))
+        })?;
+        Ok(Self { reader })
+    }
+
+    /// Look up the location of an address; `None` if unknown or without coordinates
+    pub fn locate(&self, ip: IpAddr) -> Option<GeoLocation> {
+        let city: geoip2::City = self.reader.lookup(ip).ok()?.decode().ok()??;
+
+        Some(GeoLocation {
+            lat: city.location.latitude?,
+            lon: city.location.longitude?,
+            city: city.city.names.english.map(str::to_string),
+            country: city.country.iso_code.map(str::to_string),
+            tz: city.location.time_zone.and_then(|tz| tz.parse().ok()),
+        })
+    }
+}
+
+/// Remote location with one or more established connections
+pub struct LiveEndpoint {
+    pub name: String,
+    pub label: String,
+    pub lat: f64,
+    pub lon: f64,
+    pub tz: Option<Tz>,
+    pub connections: usize,
+}
+
+/// One sample of the established connections
+pub struct LiveSnapshot {
+    pub endpoints: Vec<LiveEndpoint>,
+    /// Connections to private and loopback addresses, grouped at home
+    pub local: usize,
+    /// Public addresses the GeoIP database could not place
+    pub unresolved: usize,
+}
+
+/// Samples established TCP connections and geolocates their remote ends
+pub struct LiveConnections {
+    geoip: GeoIp,
+    catalogue: Catalogue,
+}
+
+impl LiveConnections {
+    pub fn new(geoip: GeoIp, catalogue: Catalogue) -> Self {
+        Self { geoip, catalogue }
+    }
+
+    pub fn sample(&self) -> LiveSnapshot {
+        let mut snapshot = LiveSnapshot {
+            endpoints: Vec::new(),
+            local: 0,
+            unresolved: 0,
+        };
+        let mut by_name: HashMap<String, usize> = HashMap::new();
+
+        for ip in established_remotes() {
+            if is_local(ip) {
+                snapshot.local += 1;
+                continue;
+            }
+
+            let Some(location) = self.geoip.locate(ip) else {
+                snapshot.unresolved += 1;
+                continue;
+            };
+
+            let endpoint = self.endpoint_for(&location);
+            match by_name.get(&endpoint.name) {
+                Some(&index) => snapshot.endpoints[index].connections += 1,
+                None => {
+                    by_name.insert(endpoint.name.clone(), snapshot.endpoints.len());
+                    snapshot.endpoints.push(endpoint);
+                }
+            }
+        }
+
+        // Busiest locations first, so they keep their place on the map
+        snapshot.endpoints.sort_by(|a, b| b.connections.cmp(&a.connections).then_with(|| a.name.cmp(&b.name)));
+        snapshot
+    }
+
+    /// Name and label a location, preferring a nearby catalogue city
+    fn endpoint_for(&self, location: &GeoLocation) -> LiveEndpoint {
+        if let Some(entry) = self.catalogue.nearest(location.lat, location.lon, NEAREST_CITY_KM) {
+            return LiveEndpoint {
+                name: entry.name.clone(),
+                label: entry.label.clone(),
+                lat: entry.lat,
+                lon: entry.lon,
+                tz: entry.tz.or(location.tz),
+                connections: 1,
+            };
+        }
+
+        let country = location.country.as_deref().unwrap_or("??");
+        let (name, label) = match &location.city {
+            Some(city) => (
+                format!("{city}, {country}"),
+                city.chars().filter(|c| c.is_alphabetic()).take(3).collect::<String>().to_uppercase(),
+            ),
+            None => (country.to_string(), country.to_string()),
+        };
+
+        LiveEndpoint {
+            name,
+            label,
+            lat: location.lat,
+            lon: location.lon,
+            tz: location.tz,
+            connections: 1,
+        }
+    }
+}
+
+/// Remote addresses of all established TCP connections (IPv4 and IPv6)
+fn established_remotes() -> Vec<IpAddr> {
+    let mut remotes = Vec::new();
+
+    for path in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        if let Ok(text) = fs::read_to_string(path) {
+            remotes.extend(parse_proc_net_tcp(&text));
+        }
+    }
+
+    remotes
+}
+
+/// Parse the table format of `/proc/net/tcp` and `/proc/net/tcp6`
+fn parse_proc_net_tcp(text: &str) -> Vec<IpAddr> {
+    text.lines()
+        .skip(1) // header
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (remote, state) = (fields.get(2)?, fields.get(3)?);
+            if *state != TCP_ESTABLISHED {
+                return None;
+            }
+
+            let (address, _port) = remote.split_once(':')?;
+            parse_hex_address(address)
+        })
+        .collect()
+}
+
+/// Addresses are printed as 32-bit words in host byte order
+fn parse_hex_address(hex: &str) -> Option<IpAddr> {
+    let word = |i: usize| -> Option<[u8; 4]> {
+        let value = u32::from_str_radix(hex.get(i * 8..i * 8 + 8)?, 16).ok()?;
+        Some(value.to_ne_bytes())
+    };
+
+    match hex.len() {
+        8 => Some(IpAddr::V4(Ipv4Addr::from(word(0)?))),
+        32 => {
+            let mut bytes = [0u8; 16];
+            for i in 0..4 {
+                bytes[i * 4..i * 4 + 4].copy_from_slice(&word(i)?);
+            }
+            let v6 = Ipv6Addr::from(bytes);
+            // IPv4 connections on dual-stack sockets show up as ::ffff:a.b.c.d
+            Some(match v6.to_ipv4_mapped() {
+                Some(v4) => IpAddr::V4(v4),
+                None => IpAddr::V6(v6),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Loopback, private and link-local addresses stay at home
+fn is_local(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_loopback() || v4.is_private() || v4.is_link_local() || v4.is_unspecified(),
+        IpAddr::V6(v6) => {
+            let first = v6.segments()[0];
+            v6.is_loopback()
+                || v6.is_unspecified()
+                || (first & 0xfe00) == 0xfc00 // unique local
+                || (first & 0xffc0) == 0xfe80 // link-local
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An address as the kernel prints it, in host byte order
+    fn hex(octets: &[u8]) -> String {
+        octets
+            .chunks(4)
+            .map(|word| format!("{:08X}", u32::from_ne_bytes(word.try_into().unwrap())))
+            .collect()
+    }
+
+    #[test]
+    fn parses_ipv4_and_ipv6_addresses() {
+        let v4 = Ipv4Addr::new(93, 184, 216, 34);
+        assert_eq!(parse_hex_address(&hex(&v4.octets())), Some(IpAddr::V4(v4)));
+
+        let v6: Ipv6Addr = "2001:db8::1".parse().unwrap();
+        assert_eq!(parse_hex_address(&hex(&v6.octets())), Some(IpAddr::V6(v6)));
+    }
+
+    #[test]
+    fn unmaps_ipv4_on_dual_stack_sockets() {
+        let mapped = Ipv4Addr::new(8, 8, 4, 4).to_ipv6_mapped();
+        assert_eq!(parse_hex_address(&hex(&mapped.octets())), Some(IpAddr::V4(Ipv4Addr::new(8, 8, 4, 4))));
+    }
+
+    #[test]
+    fn rejects_malformed_addresses() {
+        for text in ["", "0100007", "0100007F00", "ZZ00007F", &"0".repeat(31)] {
+            assert_eq!(parse_hex_address(text), None, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn keeps_only_established_remote_addresses() {
+        let (local, remote, listening) = (hex(&[10, 0, 0, 2]), hex(&[93, 184, 216, 34]), hex(&[0; 4]));
+        let text = format!(
+            "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+             \x20  0: {local}:0016 {listening}:0000 0A 00000000:00000000 00:00000000 00000000     0        0 1\n\
+             \x20  1: {local}:C350 {remote}:01BB 01 00000000:00000000 02:000A7C23 00000000  1000        0 2\n\
+             \x20  2: {local}:C351 {remote}:0050 06 00000000:00000000 03:00000F6B 00000000     0        0 0\n\
+             \x20  3: truncated\n"
+        );
+        assert_eq!(parse_proc_net_tcp(&text), vec![IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34))]);
+    }
+}
@@ -1,2 +1,8 @@
+pub mod catalogue;
+pub mod coastline;
+pub mod live_connections;
+pub mod progress_feed;
+pub mod sampler;
 pub mod system_stats;
+pub mod time_source;
 pub mod fake_data;
@@ -0,0 +1,235 @@
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, IsTerminal, Read},
+    path::{Path, PathBuf},
+    process::{self, Child, ExitStatus, Stdio},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use regex::Regex;
+
+/// How often a growing file is checked
+const FILE_POLL: Duration = Duration::from_millis(250);
+
+/// Progress reported by a feed
+#[derive(Clone, Debug, PartialEq)]
+pub enum FeedUpdate {
+    /// Progress from 0.0 to 1.0, with the label it was reported under
+    Progress { label: Option<String>, fraction: f64 },
+    /// The source is done, successfully or not
+    Finished { success: bool },
+}
+
+/// Real progress read on background threads, so slow files, pipes and
+/// commands never hold up a frame
+pub struct ProgressFeed {
+    receiver: mpsc::Receiver<FeedUpdate>,
+    /// Command being followed; killed when the feed is dropped
+    child: Option<Child>,
+    /// Exit status of the command, held back until its output is read
+    exit: Option<ExitStatus>,
+    /// Threads reading the command's output to the end
+    readers: Vec<thread::JoinHandle<bool>>,
+}
+
+impl ProgressFeed {
+    /// A file growing toward `size` bytes
+    pub fn file(path: PathBuf, size: f64) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || loop {
+            let length = fs::metadata(&path).map_or(0, |meta| meta.len());
+            let fraction = (length as f64 / size).min(1.0);
+            if sender.send(FeedUpdate::Progress { label: None, fraction }).is_err() || fraction >= 1.0 {
+                return;
+            }
+            thread::sleep(FILE_POLL);
+        });
+
+        Self {
+            receiver,
+            child: None,
+            exit: None,
+            readers: Vec::new(),
+        }
+    }
+
+    /// `label percent` lines from a named pipe, or from stdin for `-`. A pipe
+    /// is opened again after each writer closes it.
+    pub fn lines(path: PathBuf) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            if path == Path::new("-") {
+                // A terminal on stdin is where the keys come from
+                if !io::stdin().is_terminal() {
+                    forward_lines(io::stdin().lock(), &sender, parse_line);
+                }
+                return;
+            }
+            // Opening blocks until a writer shows up
+            while let Ok(file) = fs::File::open(&path) {
+                if !forward_lines(BufReader::new(file), &sender, parse_line) {
+                    return;
+                }
+                if !is_fifo(&path) {
+                    return;
+                }
+            }
+        });
+
+        Self {
+            receiver,
+            child: None,
+            exit: None,
+            readers: Vec::new(),
+        }
+    }
+
+    /// A shell command whose output lines are matched against `pattern`:
+    /// one group is a percentage, two are done and total
+    pub fn command(command: &str, pattern: Regex) -> io::Result<Self> {
+        let mut shell = process::Command::new("sh");
+        shell
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        // In a process group of its own, so stopping it reaches everything it started
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut shell, 0);
+        let mut child = shell.spawn()?;
+        let (sender, receiver) = mpsc::channel();
+
+        // Tools print progress to either stream
+        let outputs: [Option<Box<dyn Read + Send>>; 2] = [
+            child.stdout.take().map(|out| Box::new(out) as Box<dyn Read + Send>),
+            child.stderr.take().map(|err| Box::new(err) as Box<dyn Read + Send>),
+        ];
+        let readers = outputs
+            .into_iter()
+            .flatten()
+            .map(|output| {
+                let sender = sender.clone();
+                let pattern = pattern.clone();
+                thread::spawn(move || {
+                    forward_lines(BufReader::new(output), &sender, |line| {
+                        match_progress(&pattern, line).map(|fraction| (None, fraction))
+                    })
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            receiver,
+            child: Some(child),
+            exit: None,
+            readers,
+        })
+    }
+
+    /// Updates since the last call, without waiting
+    pub fn updates(&mut self) -> Vec<FeedUpdate> {
+        if let (Some(child), None) = (&mut self.child, self.exit) {
+            self.exit = child.try_wait().ok().flatten();
+        }
+        // The exit is reported once the output is read to the end, so the
+        // last progress written before it is not lost
+        let drained = self.readers.iter().all(|reader| reader.is_finished());
+        let mut updates: Vec<FeedUpdate> = self.receiver.try_iter().collect();
+
+        if let (Some(status), true) = (self.exit, drained) {
+            self.child = None;
+            self.exit = None;
+            self.readers.clear();
+            updates.push(FeedUpdate::Finished { success: status.success() });
+        }
+        updates
+    }
+}
+
+impl Drop for ProgressFeed {
+    fn drop(&mut self) {
+        if let Some(child) = &mut self.child {
+            kill_group(child);
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Send the progress parsed from each line; false once nobody listens.
+/// Lines end at a carriage return too, as progress meters redraw in place.
+fn forward_lines(
+    reader: impl BufRead,
+    sender: &mpsc::Sender<FeedUpdate>,
+    parse: impl Fn(&str) -> Option<(Option<String>, f64)>,
+) -> bool {
+    let mut line = Vec::new();
+    for byte in reader.bytes().map_while(Result::ok).chain([b'\n']) {
+        if byte != b'\n' && byte != b'\r' {
+            line.push(byte);
+            continue;
+        }
+        let text = String::from_utf8_lossy(&line);
+        if let Some((label, fraction)) = parse(&text) {
+            if sender.send(FeedUpdate::Progress { label, fraction }).is_err() {
+                return false;
+            }
+        }
+        line.clear();
+    }
+    true
+}
+
+/// A `label percent` line such as `BUILD 42` or `DEPLOY EU 73.5%`; the label
+/// may be left out
+pub fn parse_line(line: &str) -> Option<(Option<String>, f64)> {
+    let line = line.trim();
+    let (label, percent) = match line.rsplit_once(char::is_whitespace) {
+        Some((label, percent)) => (Some(label.trim().to_string()), percent),
+        None => (None, line),
+    };
+    let percent: f64 = percent.trim_end_matches('%').parse().ok()?;
+    Some((label, (percent / 100.0).clamp(0.0, 1.0)))
+}
+
+/// Progress in a line of command output, if the pattern matches
+fn match_progress(pattern: &Regex, line: &str) -> Option<f64> {
+    let captures = pattern.captures(line)?;
+    let number = |index: usize| captures.get(index).and_then(|group| group.as_str().parse::<f64>().ok());
+
+    let fraction = match (number(1), number(2)) {
+        (Some(done), Some(total)) if total > 0.0 => done / total,
+        (Some(percent), None) => percent / 100.0,
+        _ => return None,
+    };
+    Some(fraction.clamp(0.0, 1.0))
+}
+
+/// Stop a followed command and the processes it started
+#[cfg(unix)]
+fn kill_group(child: &mut Child) {
+    // The command leads its own process group, and has not been waited for yet
+    unsafe {
+        libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_group(child: &mut Child) {
+    let _ = child.kill();
+}
+
+#[cfg(unix)]
+fn is_fifo(path: &Path) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    fs::metadata(path).is_ok_and(|meta| meta.file_type().is_fifo())
+}
+
+#[cfg(not(unix))]
+fn is_fifo(_path: &Path) -> bool {
+    false
+}
@@ -0,0 +1,83 @@
+use std::{
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::data::{
+    live_connections::{LiveConnections, LiveSnapshot},
+    system_stats::{Subsystems, SystemSampler, SystemStats},
+};
+
+/// Samples system statistics on a background thread, so slow sysinfo
+/// refreshes never hold up a frame
+pub struct StatsSampler {
+    /// Snapshots from the worker, oldest first
+    receiver: mpsc::Receiver<SystemStats>,
+    /// Parts the worker refreshes on its next sample
+    wanted: Arc<Mutex<Subsystems>>,
+}
+
+impl StatsSampler {
+    /// Start sampling every `interval` seconds
+    pub fn new(interval: f64, wanted: Subsystems) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let wanted = Arc::new(Mutex::new(wanted));
+        let worker_wanted = Arc::clone(&wanted);
+        let period = Duration::from_secs_f64(interval);
+
+        thread::spawn(move || {
+            let mut sampler = SystemSampler::new(interval);
+            loop {
+                let started = Instant::now();
+                let wanted = *worker_wanted.lock().expect("sampler lock poisoned");
+                if sender.send(sampler.sample(wanted)).is_err() {
+                    return;
+                }
+                thread::sleep(period.saturating_sub(started.elapsed()));
+            }
+        });
+
+        Self { receiver, wanted }
+    }
+
+    /// Change the parts refreshed from the next sample on
+    pub fn set_wanted(&self, wanted: Subsystems) {
+        *self.wanted.lock().expect("sampler lock poisoned") = wanted;
+    }
+
+    /// The newest snapshot since the last call, without waiting
+    pub fn latest(&self) -> Option<SystemStats> {
+        self.receiver.try_iter().last()
+    }
+}
+
+/// Samples live connections on a background thread, so reading
+/// `/proc/net` and the GeoIP lookups never hold up a frame
+pub struct LiveSampler {
+    /// Snapshots from the worker, oldest first
+    receiver: mpsc::Receiver<LiveSnapshot>,
+}
+
+impl LiveSampler {
+    /// Start sampling every `interval` seconds
+    pub fn new(live: LiveConnections, interval: f64) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let period = Duration::from_secs_f64(interval);
+
+        thread::spawn(move || loop {
+            let started = Instant::now();
+            if sender.send(live.sample()).is_err() {
+                return;
+            }
+            thread::sleep(period.saturating_sub(started.elapsed()));
+        });
+
+        Self { receiver }
+    }
+
+    /// The newest snapshot since the last call, without waiting
+    pub fn latest(&self) -> Option<LiveSnapshot> {
+        self.receiver.try_iter().last()
+    }
+}
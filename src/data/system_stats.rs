@@ -1,82 +1,421 @@
-use sysinfo::{System, Networks};
+use std::{collections::HashMap, fs, path::Path, time::Instant};
+use sysinfo::{
+    Components, Disks, Networks, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind, Users,
+};
 
-/// Real system statistics
+use crate::config::{NetworkConfig, RateUnit, UnitPrefix};
+
+/// One hardware temperature sensor reading
+#[derive(Clone, Debug)]
+pub struct SensorReading {
+    pub label: String,
+    /// Temperature in °C
+    pub temperature: f64,
+    /// Critical temperature reported by the hardware, if any
+    pub critical: Option<f64>,
+}
+
+/// Traffic of one network interface
+#[derive(Clone, Debug)]
+pub struct InterfaceReading {
+    pub name: String,
+    /// Bytes per second since the previous sample
+    pub rx: u64,
+    pub tx: u64,
+    /// Bytes since the interface came up
+    pub total_rx: u64,
+    pub total_tx: u64,
+}
+
+/// Interfaces picked by the include and exclude patterns of the network settings
+#[derive(Clone, Debug)]
+pub struct InterfaceFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl InterfaceFilter {
+    pub fn with_config(config: &NetworkConfig) -> Self {
+        Self {
+            include: config.include.clone(),
+            exclude: config.exclude.clone(),
+        }
+    }
+
+    /// Is the interface picked by the include and exclude patterns?
+    pub fn shows(&self, name: &str) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|p| matches_pattern(p, name));
+        included && !self.exclude.iter().any(|p| matches_pattern(p, name))
+    }
+}
+
+/// Space on a mounted filesystem
+#[derive(Clone, Debug)]
+pub struct FilesystemReading {
+    pub mount_point: String,
+    pub total: u64,
+    pub available: u64,
+}
+
+impl FilesystemReading {
+    pub fn used_percentage(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.total.saturating_sub(self.available) as f64 / self.total as f64) * 100.0
+    }
+}
+
+/// Bytes per second read and written by a block device
+#[derive(Clone, Debug)]
+pub struct DiskIoReading {
+    pub device: String,
+    pub read: u64,
+    pub written: u64,
+}
+
+/// One running process
+#[derive(Clone, Debug)]
+pub struct ProcessReading {
+    pub pid: u32,
+    pub name: String,
+    /// CPU usage in percent of one core
+    pub cpu: f64,
+    /// Resident memory in bytes
+    pub memory: u64,
+    pub user: String,
+}
+
+/// Parts of the system to sample
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Subsystems {
+    pub cpu: bool,
+    pub memory: bool,
+    pub network: bool,
+    pub sensors: bool,
+    pub disks: bool,
+    pub processes: bool,
+}
+
+impl Subsystems {
+    /// Parts wanted by either
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            cpu: self.cpu || other.cpu,
+            memory: self.memory || other.memory,
+            network: self.network || other.network,
+            sensors: self.sensors || other.sensors,
+            disks: self.disks || other.disks,
+            processes: self.processes || other.processes,
+        }
+    }
+}
+
+/// Snapshot of real system statistics
+#[derive(Clone, Debug, Default)]
 pub struct SystemStats {
-    system: System,
-    networks: Networks,
+    /// Parts refreshed in this snapshot; the others keep older values
+    pub sampled: Subsystems,
+    /// Seconds between samples
+    pub interval: f64,
+    /// Seconds since the previous snapshot, as measured
+    pub elapsed: f64,
     pub cpu_usage: f64,
+    /// Usage of each logical core in percent
+    pub core_usage: Vec<f64>,
+    /// Average current core frequency in MHz (0 if unknown)
+    pub cpu_frequency: u64,
+    /// 1, 5 and 15 minute load averages
+    pub load_average: [f64; 3],
     pub memory_used: u64,
     pub memory_total: u64,
-    pub network_rx: u64,
-    pub network_tx: u64,
-    last_rx: u64,
-    last_tx: u64,
+    /// Memory that can be given to programs without swapping
+    pub memory_available: u64,
+    /// Memory not used for anything
+    pub memory_free: u64,
+    /// Page cache and buffers, where the platform exposes them
+    pub memory_cached: Option<u64>,
+    pub memory_buffers: Option<u64>,
+    pub swap_used: u64,
+    pub swap_total: u64,
+    /// Network interfaces sorted by name, loopback included
+    pub interfaces: Vec<InterfaceReading>,
+    /// Temperature sensors with a valid reading
+    pub sensors: Vec<SensorReading>,
+    /// Mounted filesystems, sorted by mount point
+    pub filesystems: Vec<FilesystemReading>,
+    /// Block devices from `/proc/diskstats`, where available
+    pub disk_io: Vec<DiskIoReading>,
+    /// Running processes, threads left out
+    pub processes: Vec<ProcessReading>,
 }
 
 impl SystemStats {
-    pub fn new() -> Self {
+    pub fn memory_percentage(&self) -> f64 {
+        if self.memory_total == 0 {
+            return 0.0;
+        }
+        (self.memory_used as f64 / self.memory_total as f64) * 100.0
+    }
+}
+
+/// Takes `SystemStats` snapshots; owns the sysinfo state between samples
+pub struct SystemSampler {
+    system: System,
+    networks: Networks,
+    components: Components,
+    disks: Disks,
+    users: Users,
+    stats: SystemStats,
+    /// When the network and disk counters were last read, for their rates
+    last_network: Instant,
+    last_disk_io: Instant,
+    /// Sectors read and written per device at the previous sample
+    last_diskstats: HashMap<String, (u64, u64)>,
+    /// When the previous snapshot was taken
+    last_sample: Instant,
+}
+
+impl SystemSampler {
+    pub fn new(interval: f64) -> Self {
         let mut system = System::new_all();
         system.refresh_all();
 
-        let networks = Networks::new_with_refreshed_list();
-
-        let mut stats = Self {
+        Self {
             system,
-            networks,
-            cpu_usage: 0.0,
-            memory_used: 0,
-            memory_total: 0,
-            network_rx: 0,
-            network_tx: 0,
-            last_rx: 0,
-            last_tx: 0,
-        };
+            networks: Networks::new_with_refreshed_list(),
+            components: Components::new_with_refreshed_list(),
+            disks: Disks::new_with_refreshed_list(),
+            users: Users::new_with_refreshed_list(),
+            stats: SystemStats {
+                interval,
+                ..SystemStats::default()
+            },
+            last_network: Instant::now(),
+            last_disk_io: Instant::now(),
+            last_diskstats: parse_diskstats(&fs::read_to_string("/proc/diskstats").unwrap_or_default()),
+            last_sample: Instant::now(),
+        }
+    }
 
-        stats.refresh();
-        stats
+    /// Refresh the wanted parts and return the new snapshot
+    pub fn sample(&mut self, wanted: Subsystems) -> SystemStats {
+        self.stats.sampled = wanted;
+        self.stats.elapsed = self.last_sample.elapsed().as_secs_f64();
+        self.last_sample = Instant::now();
+        if wanted.cpu {
+            self.refresh_cpu();
+        }
+        if wanted.memory {
+            self.refresh_memory();
+        }
+        if wanted.network {
+            self.refresh_network();
+        }
+        if wanted.sensors {
+            self.refresh_sensors();
+        }
+        if wanted.disks {
+            self.refresh_disks();
+        }
+        if wanted.processes {
+            self.refresh_processes();
+        }
+        self.stats.clone()
     }
 
-    pub fn refresh(&mut self) {
-        // Refresh CPU
+    fn refresh_cpu(&mut self) {
         self.system.refresh_cpu_usage();
-        self.cpu_usage = self.system.global_cpu_usage() as f64;
+        self.system.refresh_cpu_frequency();
+        let stats = &mut self.stats;
+        stats.cpu_usage = self.system.global_cpu_usage() as f64;
 
-        // Refresh memory
+        let cpus = self.system.cpus();
+        stats.core_usage = cpus.iter().map(|cpu| cpu.cpu_usage() as f64).collect();
+        stats.cpu_frequency = match cpus.len() {
+            0 => 0,
+            n => cpus.iter().map(|cpu| cpu.frequency()).sum::<u64>() / n as u64,
+        };
+
+        let load = System::load_average();
+        stats.load_average = [load.one, load.five, load.fifteen];
+    }
+
+    fn refresh_memory(&mut self) {
         self.system.refresh_memory();
-        self.memory_used = self.system.used_memory();
-        self.memory_total = self.system.total_memory();
+        let stats = &mut self.stats;
+        stats.memory_used = self.system.used_memory();
+        stats.memory_total = self.system.total_memory();
+        stats.memory_available = self.system.available_memory();
+        stats.memory_free = self.system.free_memory();
+        stats.swap_used = self.system.used_swap();
+        stats.swap_total = self.system.total_swap();
 
-        // Refresh network
+        let meminfo = fs::read_to_string("/proc/meminfo").unwrap_or_default();
+        stats.memory_cached = meminfo_field(&meminfo, "Cached");
+        stats.memory_buffers = meminfo_field(&meminfo, "Buffers");
+    }
+
+    fn refresh_network(&mut self) {
+        // sysinfo counts the traffic since its previous refresh
         self.networks.refresh();
+        let seconds = self.last_network.elapsed().as_secs_f64().max(0.001);
+        self.last_network = Instant::now();
+
+        self.stats.interfaces = self
+            .networks
+            .iter()
+            .map(|(name, data)| InterfaceReading {
+                name: name.clone(),
+                rx: (data.received() as f64 / seconds) as u64,
+                tx: (data.transmitted() as f64 / seconds) as u64,
+                total_rx: data.total_received(),
+                total_tx: data.total_transmitted(),
+            })
+            .collect();
+        self.stats.interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    }
 
-        let mut total_rx: u64 = 0;
-        let mut total_tx: u64 = 0;
+    fn refresh_sensors(&mut self) {
+        self.components.refresh();
+        self.stats.sensors = self
+            .components
+            .iter()
+            .filter(|component| component.temperature().is_finite())
+            .map(|component| SensorReading {
+                label: component.label().to_string(),
+                temperature: component.temperature() as f64,
+                critical: component.critical().filter(|c| c.is_finite() && *c > 0.0).map(f64::from),
+            })
+            .collect();
+    }
 
-        for (_, data) in self.networks.iter() {
-            total_rx = total_rx.saturating_add(data.total_received());
-            total_tx = total_tx.saturating_add(data.total_transmitted());
-        }
+    fn refresh_disks(&mut self) {
+        self.disks.refresh();
+        self.stats.filesystems = filesystems(&self.disks);
 
-        // Calculate bytes per second (delta from last refresh)
-        self.network_rx = total_rx.saturating_sub(self.last_rx);
-        self.network_tx = total_tx.saturating_sub(self.last_tx);
+        // Disk I/O as the change of the sector counters since the last sample
+        let diskstats = fs::read_to_string("/proc/diskstats").unwrap_or_default();
+        let sectors = parse_diskstats(&diskstats);
+        let seconds = self.last_disk_io.elapsed().as_secs_f64().max(0.001);
+        self.last_disk_io = Instant::now();
 
-        self.last_rx = total_rx;
-        self.last_tx = total_tx;
+        let rate = |now: u64, before: u64| {
+            (now.saturating_sub(before) as f64 * SECTOR_SIZE as f64 / seconds) as u64
+        };
+        self.stats.disk_io = sectors
+            .iter()
+            .map(|(device, &(read, written))| {
+                let (last_read, last_written) = self.last_diskstats.get(device).copied().unwrap_or((read, written));
+                DiskIoReading {
+                    device: device.clone(),
+                    read: rate(read, last_read),
+                    written: rate(written, last_written),
+                }
+            })
+            .collect();
+        self.stats.disk_io.sort_by(|a, b| a.device.cmp(&b.device));
+        self.last_diskstats = sectors;
     }
 
-    pub fn memory_percentage(&self) -> f64 {
-        if self.memory_total == 0 {
-            return 0.0;
-        }
-        (self.memory_used as f64 / self.memory_total as f64) * 100.0
+    fn refresh_processes(&mut self) {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::new()
+                .with_cpu()
+                .with_memory()
+                .with_user(UpdateKind::OnlyIfNotSet),
+        );
+        self.stats.processes = self
+            .system
+            .processes()
+            .values()
+            .filter(|process| process.thread_kind().is_none())
+            .map(|process| ProcessReading {
+                pid: process.pid().as_u32(),
+                name: process.name().to_string_lossy().into_owned(),
+                cpu: process.cpu_usage() as f64,
+                memory: process.memory(),
+                user: process
+                    .user_id()
+                    .and_then(|uid| self.users.get_user_by_id(uid))
+                    .map_or_else(|| "?".to_string(), |user| user.name().to_string()),
+            })
+            .collect();
     }
 }
 
-impl Default for SystemStats {
-    fn default() -> Self {
-        Self::new()
+/// Filesystems with a size, sorted by mount point
+fn filesystems(disks: &Disks) -> Vec<FilesystemReading> {
+    let mut filesystems: Vec<FilesystemReading> = disks
+        .iter()
+        .filter(|disk| disk.total_space() > 0)
+        .map(|disk| FilesystemReading {
+            mount_point: disk.mount_point().display().to_string(),
+            total: disk.total_space(),
+            available: disk.available_space(),
+        })
+        .collect();
+    filesystems.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    filesystems
+}
+
+/// Mount points the sampler reports filesystems for
+pub fn mount_points() -> Vec<String> {
+    filesystems(&Disks::new_with_refreshed_list())
+        .into_iter()
+        .map(|fs| fs.mount_point)
+        .collect()
+}
+
+/// `/proc/diskstats` always counts 512-byte sectors
+const SECTOR_SIZE: u64 = 512;
+
+/// Sectors read and written by each whole block device in `/proc/diskstats`,
+/// leaving out partitions and loop, RAM and compressed-swap devices
+fn parse_diskstats(text: &str) -> HashMap<String, (u64, u64)> {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let device = *fields.get(2)?;
+            let read = fields.get(5)?.parse().ok()?;
+            let written = fields.get(9)?.parse().ok()?;
+            Some((device, (read, written)))
+        })
+        .filter(|(device, _)| {
+            !["loop", "ram", "zram"].iter().any(|prefix| device.starts_with(prefix))
+                && Path::new("/sys/block").join(device).exists()
+        })
+        .map(|(device, sectors)| (device.to_string(), sectors))
+        .collect()
+}
+
+/// A field of `/proc/meminfo` such as `Cached:   123456 kB`, in bytes
+fn meminfo_field(meminfo: &str, name: &str) -> Option<u64> {
+    meminfo.lines().find_map(|line| {
+        let value = line.strip_prefix(name)?.strip_prefix(':')?;
+        let kib: u64 = value.trim().trim_end_matches("kB").trim().parse().ok()?;
+        Some(kib * 1024)
+    })
+}
+
+/// Shell-style pattern match where `*` is any text and `?` any one character
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| matches(rest, &name[skip..])),
+            Some((&c, rest)) => match name.split_first() {
+                Some((&n, name_rest)) => (c == '?' || c == n) && matches(rest, name_rest),
+                None => false,
+            },
+        }
     }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches(&pattern, &name)
 }
 
 /// Format bytes to human readable string
@@ -96,7 +435,130 @@ pub fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// Format an amount of data in the chosen unit, e.g. "1.5 MiB" or "12.0 Mbit"
+pub fn format_amount(bytes: u64, unit: RateUnit, prefix: UnitPrefix) -> String {
+    let (mut value, symbol) = match unit {
+        RateUnit::Bytes => (bytes as f64, "B"),
+        RateUnit::Bits => (bytes as f64 * 8.0, "bit"),
+    };
+    let (base, prefixes) = match prefix {
+        UnitPrefix::Iec => (1024.0, ["", "Ki", "Mi", "Gi", "Ti"]),
+        UnitPrefix::Si => (1000.0, ["", "k", "M", "G", "T"]),
+    };
+
+    let mut index = 0;
+    while value >= base && index < prefixes.len() - 1 {
+        value /= base;
+        index += 1;
+    }
+
+    if index == 0 {
+        format!("{value:.0} {symbol}")
+    } else {
+        format!("{value:.1} {}{symbol}", prefixes[index])
+    }
+}
+
+/// An amount such as `250MB`, `1.5GiB` or `100Mbit`, in bytes
+pub fn parse_bytes(text: &str) -> Result<f64, String> {
+    let invalid = || format!("invalid size '{text}' (expected e.g. 250MB)");
+    let unit_start = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+    let (number, unit) = text.split_at(unit_start);
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+
+    let (unit, bits) = match unit.strip_suffix("bit") {
+        Some(prefix) => (prefix, true),
+        None => (unit.strip_suffix('B').unwrap_or(unit), false),
+    };
+    let (prefix, base) = match unit.strip_suffix('i') {
+        Some(prefix) => (prefix, 1024.0_f64),
+        None => (unit, 1000.0),
+    };
+    let power = match prefix {
+        "" => 0,
+        "k" | "K" => 1,
+        "M" => 2,
+        "G" => 3,
+        "T" => 4,
+        _ => return Err(invalid()),
+    };
+
+    let bytes = number * base.powi(power);
+    Ok(if bits { bytes / 8.0 } else { bytes })
+}
+
 /// Format bytes per second
-pub fn format_bytes_per_sec(bytes: u64) -> String {
-    format!("{}/s", format_bytes(bytes))
+pub fn format_bytes_per_sec(bytes: u64, unit: RateUnit, prefix: UnitPrefix) -> String {
+    format!("{}/s", format_amount(bytes, unit, prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_wildcards() {
+        let cases = [
+            ("eth0", "eth0", true),
+            ("eth0", "eth1", false),
+            ("eth*", "eth", true),
+            ("eth*", "eth0.100", true),
+            ("eth*", "veth0", false),
+            ("*eth*", "veth0", true),
+            ("wl?", "wl0", true),
+            ("wl?", "wlan0", false),
+            ("wl?*", "wl", false),
+            ("docker*", "docker0", true),
+            ("*", "", true),
+            ("", "lo", false),
+            ("br-?ä*", "br-xä1", true),
+        ];
+        for (pattern, name, expected) in cases {
+            assert_eq!(matches_pattern(pattern, name), expected, "{pattern} ~ {name}");
+        }
+    }
+
+    #[test]
+    fn filter_applies_exclude_after_include() {
+        let config = NetworkConfig {
+            include: vec!["eth*".to_string(), "wl*".to_string()],
+            exclude: vec!["eth9".to_string()],
+            ..NetworkConfig::default()
+        };
+        let filter = InterfaceFilter::with_config(&config);
+        assert!(filter.shows("eth0"));
+        assert!(filter.shows("wlan0"));
+        assert!(!filter.shows("eth9"));
+        assert!(!filter.shows("lo"));
+
+        let default = InterfaceFilter::with_config(&NetworkConfig::default());
+        assert!(default.shows("enp3s0"));
+        assert!(!default.shows("lo"));
+        assert!(!default.shows("br-5f2a"));
+    }
+
+    #[test]
+    fn parses_byte_amounts() {
+        let cases = [
+            ("0", 0.0),
+            ("512B", 512.0),
+            ("1.5kB", 1500.0),
+            ("250MB", 250e6),
+            ("2GiB", 2.0 * 1024.0 * 1024.0 * 1024.0),
+            ("1KiB", 1024.0),
+            ("100Mbit", 12.5e6),
+            ("8bit", 1.0),
+            ("3T", 3e12),
+        ];
+        for (text, bytes) in cases {
+            assert_eq!(parse_bytes(text), Ok(bytes), "{text}");
+        }
+    }
+
+    #[test]
+    fn rejects_bad_byte_amounts() {
+        for text in ["", "MB", "12 MB", "1.2.3MB", "5PB", "5mb", "-5MB", "10B/s"] {
+            assert_eq!(parse_bytes(text), Err(format!("invalid size '{text}' (expected e.g. 250MB)")), "{text}");
+        }
+    }
 }
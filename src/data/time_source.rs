@@ -0,0 +1,60 @@
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use std::io;
+
+use crate::config::TimeConfig;
+use crate::TICKS_PER_SECOND;
+
+/// Wall-clock time shown by the dashboard. With a start time configured the
+/// time is virtual: it advances by a fixed step per tick, so recordings show
+/// the same times on every run.
+pub struct TimeSource {
+    start: Option<DateTime<Utc>>,
+    /// Virtual seconds per real second
+    speed: f64,
+    ticks: u64,
+}
+
+impl TimeSource {
+    pub fn new(config: &TimeConfig) -> io::Result<Self> {
+        let start = config.start.as_deref().map(parse_start).transpose()?;
+        Ok(Self {
+            start,
+            speed: config.speed,
+            ticks: 0,
+        })
+    }
+
+    pub fn tick(&mut self) {
+        self.ticks += 1;
+    }
+
+    /// The current real or virtual time
+    pub fn now(&self) -> DateTime<Utc> {
+        match self.start {
+            Some(start) => {
+                let millis = self.ticks as f64 * 1000.0 / TICKS_PER_SECOND * self.speed;
+                start + Duration::milliseconds(millis as i64)
+            }
+            None => Utc::now(),
+        }
+    }
+}
+
+impl Default for TimeSource {
+    fn default() -> Self {
+        Self::new(&TimeConfig::default()).expect("default time config is valid")
+    }
+}
+
+/// RFC 3339 (`2026-03-01T22:00:00+01:00`), or a plain UTC date and time
+fn parse_start(text: &str) -> io::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(text)
+        .map(|time| time.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").map(|time| time.and_utc()))
+        .map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid start time '{text}' (expected e.g. 2026-03-01T22:00:00Z)"),
+            )
+        })
+}
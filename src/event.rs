@@ -47,20 +47,16 @@ impl EventHandler {
                 // Poll for events
                 if event::poll(timeout).expect("Failed to poll events") {
                     match event::read().expect("Failed to read event") {
-                        CrosstermEvent::Key(key) => {
-                            if event_sender.send(Event::Key(key)).is_err() {
-                                return;
-                            }
+                        CrosstermEvent::Key(key) if event_sender.send(Event::Key(key)).is_err() => {
+                            return;
                         }
-                        CrosstermEvent::Mouse(mouse) => {
-                            if event_sender.send(Event::Mouse(mouse)).is_err() {
-                                return;
-                            }
+                        CrosstermEvent::Mouse(mouse) if event_sender.send(Event::Mouse(mouse)).is_err() => {
+                            return;
                         }
-                        CrosstermEvent::Resize(width, height) => {
-                            if event_sender.send(Event::Resize(width, height)).is_err() {
-                                return;
-                            }
+                        CrosstermEvent::Resize(width, height)
+                            if event_sender.send(Event::Resize(width, height)).is_err() =>
+                        {
+                            return;
                         }
                         _ => {}
                     }
@@ -83,6 +79,6 @@ impl EventHandler {
     pub fn next(&self) -> io::Result<Event> {
         self.receiver
             .recv()
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            .map_err(io::Error::other)
     }
 }
@@ -1,5 +1,9 @@
+mod alerts;
 mod app;
+mod command;
+mod config;
 mod event;
+mod scenario;
 mod ui;
 mod widgets;
 mod data;
@@ -8,24 +12,39 @@ use std::io;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
+    style::Print,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
 
 use app::App;
+use config::{Args, Config};
 use event::{Event, EventHandler};
 
+/// Ticks per second of the main loop; animations and delays count in ticks
+pub const TICKS_PER_SECOND: f64 = 60.0;
+
 fn main() -> io::Result<()> {
+    // Load configuration before touching the terminal so errors stay readable
+    let mut app = match startup() {
+        Ok(app) => app,
+        Err(err) => {
+            eprintln!("future: {err}");
+            std::process::exit(1);
+        }
+    };
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
+    let size = terminal.size()?;
+    app.handle_resize(size.width, size.height);
 
-    // Create app and event handler
-    let mut app = App::new();
-    let event_handler = EventHandler::new(16); // 60 FPS (~16ms tick)
+    // Create event handler
+    let event_handler = EventHandler::new((1000.0 / TICKS_PER_SECOND) as u64); // ~16ms tick
 
     // Main loop
     let result = run_app(&mut terminal, &mut app, &event_handler);
@@ -46,6 +65,13 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+/// Parse arguments, load the config and build the app
+fn startup() -> io::Result<App> {
+    let args = Args::parse()?;
+    let config = Config::load(&args)?;
+    App::new(&config)
+}
+
 fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
@@ -54,6 +80,9 @@ fn run_app<B: Backend>(
     while app.running {
         // Draw UI
         terminal.draw(|frame| ui::render(frame, app))?;
+        if std::mem::take(&mut app.bell) {
+            execute!(io::stdout(), Print('\x07'))?;
+        }
 
         // Handle events
         match event_handler.next()? {
@@ -66,7 +95,9 @@ fn run_app<B: Backend>(
             Event::Resize(width, height) => {
                 app.handle_resize(width, height);
             }
-            Event::Mouse(_) => {}
+            Event::Mouse(mouse_event) => {
+                app.handle_mouse_event(mouse_event);
+            }
         }
     }
 
@@ -0,0 +1,159 @@
+use std::{fs, io, path::Path};
+
+use crate::command::Command;
+use crate::config::ScenarioFile;
+use crate::TICKS_PER_SECOND;
+
+/// A scenario step: commands run together
+#[derive(Debug, Clone)]
+pub struct Cue {
+    pub name: String,
+    /// Ticks after the previous cue
+    after: u64,
+    /// Runs on the next-cue key only
+    wait: bool,
+    pub commands: Vec<Command>,
+}
+
+/// Plays the cues of a scenario file in order
+pub struct Scenario {
+    pub cues: Vec<Cue>,
+    /// Index of the cue to run next
+    pub next: usize,
+    /// Ticks since the last cue ran, or since the start
+    waited: u64,
+}
+
+impl Scenario {
+    /// Read a scenario file; every command is checked up front
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        let invalid = |message: String| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {message}", path.display()))
+        };
+        let file: ScenarioFile = toml::from_str(&text).map_err(|e| invalid(e.to_string()))?;
+
+        let cues = file
+            .cues
+            .into_iter()
+            .enumerate()
+            .map(|(index, cue)| {
+                let name = cue.name.unwrap_or_else(|| format!("cue {}", index + 1));
+                if !cue.after.is_finite() || cue.after < 0.0 {
+                    return Err(invalid(format!("{name}: invalid delay {}", cue.after)));
+                }
+                let commands = cue
+                    .run
+                    .iter()
+                    .map(|line| Command::parse(line))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| invalid(format!("{name}: {e}")))?;
+
+                Ok(Cue {
+                    name,
+                    after: (cue.after * TICKS_PER_SECOND).round() as u64,
+                    wait: cue.wait,
+                    commands,
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            cues,
+            next: 0,
+            waited: 0,
+        })
+    }
+
+    /// Check each command with `known`, e.g. for names that must exist
+    pub fn check(&self, known: impl Fn(&Command) -> Result<(), String>) -> Result<(), String> {
+        for cue in &self.cues {
+            for command in &cue.commands {
+                known(command).map_err(|e| format!("{}: {e}", cue.name))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Cues whose delay ran out this tick, in order
+    pub fn tick(&mut self) -> Vec<usize> {
+        self.waited += 1;
+
+        let mut due = Vec::new();
+        while let Some(cue) = self.cues.get(self.next) {
+            if cue.wait || self.waited < cue.after {
+                break;
+            }
+            due.push(self.advance());
+        }
+        due
+    }
+
+    /// Run the next cue now, whether it waits for a key or a delay
+    pub fn next_cue(&mut self) -> Option<usize> {
+        (self.next < self.cues.len()).then(|| self.advance())
+    }
+
+    /// Run the cue before the last one again and carry on from there
+    pub fn previous_cue(&mut self) -> Option<usize> {
+        self.next = self.next.saturating_sub(2);
+        self.next_cue()
+    }
+
+    fn advance(&mut self) -> usize {
+        let index = self.next;
+        self.next += 1;
+        self.waited = 0;
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario(cues: &[(&str, &[&str])]) -> Scenario {
+        let cues = cues
+            .iter()
+            .map(|(name, commands)| Cue {
+                name: name.to_string(),
+                after: 0,
+                wait: false,
+                commands: commands.iter().map(|line| Command::parse(line).unwrap()).collect(),
+            })
+            .collect();
+        Scenario {
+            cues,
+            next: 0,
+            waited: 0,
+        }
+    }
+
+    /// Only the layouts `default` and `launch` exist
+    fn known_layout(command: &Command) -> Result<(), String> {
+        match command {
+            Command::Layout(name) if !["default", "launch"].contains(&name.as_str()) => {
+                Err(format!("unknown layout '{name}'"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    #[test]
+    fn check_passes_known_names() {
+        let scenario = scenario(&[("intro", &["reveal", "layout launch"]), ("outro", &["layout default"])]);
+        assert_eq!(scenario.check(known_layout), Ok(()));
+        assert_eq!(self::scenario(&[]).check(known_layout), Ok(()));
+    }
+
+    #[test]
+    fn check_names_the_cue_of_the_first_unknown_name() {
+        let scenario = scenario(&[
+            ("intro", &["layout launch"]),
+            ("breach", &["log alert core breach", "layout alarm"]),
+            ("outro", &["layout finale"]),
+        ]);
+        assert_eq!(scenario.check(known_layout), Err("breach: unknown layout 'alarm'".to_string()));
+    }
+}
@@ -0,0 +1,227 @@
+use ratatui::prelude::*;
+use std::io;
+
+use crate::config::{LayoutConfig, LayoutFile};
+
+/// Layouts that ship with the app
+const BUNDLED_LAYOUTS: &str = include_str!("../../assets/layouts.toml");
+
+/// Dashboard panels
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Panel {
+    Clock,
+    Analog,
+    Title,
+    Countdown,
+    Timers,
+    Matrix,
+    Source,
+    Map,
+    Cpu,
+    Memory,
+    Network,
+    Sensors,
+    Disks,
+    Processes,
+    Logs,
+    HexDump,
+    Progress,
+}
+
+impl Panel {
+    const ALL: [Panel; 17] = [
+        Panel::Clock,
+        Panel::Analog,
+        Panel::Title,
+        Panel::Countdown,
+        Panel::Timers,
+        Panel::Matrix,
+        Panel::Source,
+        Panel::Map,
+        Panel::Cpu,
+        Panel::Memory,
+        Panel::Network,
+        Panel::Sensors,
+        Panel::Disks,
+        Panel::Processes,
+        Panel::Logs,
+        Panel::HexDump,
+        Panel::Progress,
+    ];
+
+    /// Name used in layout definitions
+    pub fn name(self) -> &'static str {
+        match self {
+            Panel::Clock => "clock",
+            Panel::Analog => "analog",
+            Panel::Title => "title",
+            Panel::Countdown => "countdown",
+            Panel::Timers => "timers",
+            Panel::Matrix => "matrix",
+            Panel::Source => "source",
+            Panel::Map => "map",
+            Panel::Cpu => "cpu",
+            Panel::Memory => "memory",
+            Panel::Network => "network",
+            Panel::Sensors => "sensors",
+            Panel::Disks => "disks",
+            Panel::Processes => "processes",
+            Panel::Logs => "logs",
+            Panel::HexDump => "hexdump",
+            Panel::Progress => "progress",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Panel> {
+        Self::ALL.into_iter().find(|panel| panel.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Can the panel take keyboard focus?
+    pub fn is_focusable(self) -> bool {
+        self != Panel::Title
+    }
+}
+
+/// A column of panels stacked top to bottom
+#[derive(Clone, Debug)]
+struct Column {
+    width: Constraint,
+    cells: Vec<(Panel, Constraint)>,
+}
+
+/// A row of columns
+#[derive(Clone, Debug)]
+struct Row {
+    height: Constraint,
+    columns: Vec<Column>,
+}
+
+/// A named arrangement of panels: rows, split into columns, split into cells
+#[derive(Clone, Debug)]
+pub struct DashboardLayout {
+    pub name: String,
+    rows: Vec<Row>,
+}
+
+impl DashboardLayout {
+    /// The bundled layouts, followed by the configured ones; a configured
+    /// layout replaces a bundled one with the same name
+    pub fn load_all(configured: &[LayoutConfig]) -> io::Result<Vec<Self>> {
+        let bundled: LayoutFile = toml::from_str(BUNDLED_LAYOUTS).expect("bundled layouts are valid");
+        let mut layouts: Vec<Self> = Vec::new();
+
+        for config in bundled.layouts.iter().chain(configured) {
+            let layout = Self::from_config(config).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidInput, format!("layout '{}': {e}", config.name))
+            })?;
+            match layouts.iter_mut().find(|l| l.name.eq_ignore_ascii_case(&layout.name)) {
+                Some(existing) => *existing = layout,
+                None => layouts.push(layout),
+            }
+        }
+
+        Ok(layouts)
+    }
+
+    /// Index of the layout with a name, ignoring case
+    pub fn find(layouts: &[Self], name: &str) -> Option<usize> {
+        layouts.iter().position(|layout| layout.name.eq_ignore_ascii_case(name))
+    }
+
+    fn from_config(config: &LayoutConfig) -> Result<Self, String> {
+        if config.rows.is_empty() {
+            return Err("no rows".to_string());
+        }
+
+        let rows = config
+            .rows
+            .iter()
+            .map(|row| {
+                if !row.widths.is_empty() && row.widths.len() != row.columns.len() {
+                    return Err(format!(
+                        "{} widths for {} columns",
+                        row.widths.len(),
+                        row.columns.len()
+                    ));
+                }
+
+                let columns = row
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, cells)| {
+                        let cells = cells.iter().map(|cell| parse_cell(cell)).collect::<Result<_, _>>()?;
+                        Ok(Column {
+                            width: parse_size(row.widths.get(i).map(String::as_str))?,
+                            cells,
+                        })
+                    })
+                    .collect::<Result<_, String>>()?;
+
+                Ok(Row {
+                    height: parse_size(row.height.as_deref())?,
+                    columns,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self {
+            name: config.name.clone(),
+            rows,
+        })
+    }
+
+    /// Screen area of every panel in the layout
+    pub fn areas(&self, area: Rect) -> Vec<(Panel, Rect)> {
+        let mut areas = Vec::new();
+        let row_areas = Layout::vertical(self.rows.iter().map(|row| row.height)).split(area);
+
+        for (row, &row_area) in self.rows.iter().zip(row_areas.iter()) {
+            let column_areas = Layout::horizontal(row.columns.iter().map(|c| c.width)).split(row_area);
+
+            for (column, &column_area) in row.columns.iter().zip(column_areas.iter()) {
+                let cell_areas = Layout::vertical(column.cells.iter().map(|&(_, size)| size)).split(column_area);
+                areas.extend(column.cells.iter().map(|&(panel, _)| panel).zip(cell_areas.iter().copied()));
+            }
+        }
+
+        areas
+    }
+
+    /// Panels in the layout, top left to bottom right
+    pub fn panels(&self) -> Vec<Panel> {
+        self.rows
+            .iter()
+            .flat_map(|row| &row.columns)
+            .flat_map(|column| column.cells.iter().map(|&(panel, _)| panel))
+            .collect()
+    }
+
+    pub fn contains(&self, panel: Panel) -> bool {
+        self.panels().contains(&panel)
+    }
+}
+
+/// A cell such as `cpu`, `cpu:4` or `matrix:50%`
+fn parse_cell(cell: &str) -> Result<(Panel, Constraint), String> {
+    let (name, size) = match cell.split_once(':') {
+        Some((name, size)) => (name.trim(), Some(size.trim())),
+        None => (cell.trim(), None),
+    };
+
+    let panel = Panel::from_name(name).ok_or_else(|| format!("unknown panel '{name}'"))?;
+    Ok((panel, parse_size(size)?))
+}
+
+/// `N` lines or columns, `N%` of the space, or the remaining space if unset
+fn parse_size(size: Option<&str>) -> Result<Constraint, String> {
+    let Some(size) = size else {
+        return Ok(Constraint::Fill(1));
+    };
+
+    let invalid = || format!("invalid size '{size}' (expected e.g. 10 or 40%)");
+    match size.strip_suffix('%') {
+        Some(percent) => percent.trim().parse().map(Constraint::Percentage).map_err(|_| invalid()),
+        None => size.parse().map(Constraint::Length).map_err(|_| invalid()),
+    }
+}
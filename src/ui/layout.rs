@@ -4,23 +4,30 @@ use ratatui::{
 };
 
 use crate::app::App;
+use crate::config::Projection;
+use crate::ui::dashboard::{DashboardLayout, Panel};
 use crate::ui::theme::*;
 use crate::widgets::{
-    clock::render_clock,
-    countdown_timer::render_countdown,
+    clock::{render_analog_clock, render_clock},
+    countdown_timer::{render_countdown, render_timers},
     cpu_gauge::render_cpu_gauge,
+    disk_monitor::render_disk_monitor,
     fake_logs::render_fake_logs,
+    globe::render_globe,
     hex_dump::render_hex_dump,
     matrix_rain::render_matrix_rain,
     memory_gauge::render_memory_gauge,
     network_monitor::render_network_monitor,
+    process_table::render_process_table,
     progress_bars::render_progress_bars,
+    self_destruct::render_self_destruct,
+    sensors::render_sensors,
     source_code::render_source_code,
     world_map::render_world_map,
 };
 
 /// Create a neon-styled block with title
-pub fn neon_block(title: &str) -> Block {
+pub fn neon_block(title: &str) -> Block<'_> {
     Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
@@ -39,24 +46,26 @@ pub fn render(frame: &mut Frame, app: &App) {
         return;
     }
 
-    // Main vertical layout
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(5),  // Header (clock, status, countdown)
-            Constraint::Min(15),    // Main content area
-            Constraint::Length(10), // Footer (hex dump, progress)
-        ])
-        .split(area);
-
-    // Render header
-    render_header(frame, app, main_chunks[0]);
+    // Panels with a firing alert rule flash red about twice a second
+    let alarmed = app.alerts.firing_panels();
+    let flash_on = (app.tick_count / 15).is_multiple_of(2);
+
+    for (panel, rect) in panel_areas(area, app.current_layout(), app.zoomed_panel()) {
+        render_panel(frame, app, panel, rect);
+        if alarmed.contains(&panel) && flash_on {
+            highlight_border(frame.buffer_mut(), rect, NEON_RED);
+        } else if app.focus == Some(panel) {
+            highlight_border(frame.buffer_mut(), rect, BORDER_ACTIVE);
+        }
+    }
 
-    // Render main content
-    render_main_content(frame, app, main_chunks[1]);
+    // Self destruct effect over the whole dashboard
+    render_self_destruct(frame, &app.self_destruct, area);
 
-    // Render footer
-    render_footer(frame, app, main_chunks[2]);
+    // Render alert banner if a rule or command asked for one
+    if let Some(banner) = &app.banner {
+        render_alert_banner(frame, &banner.title, &banner.message, area);
+    }
 
     // Render help overlay if active
     if app.show_help {
@@ -67,21 +76,68 @@ pub fn render(frame: &mut Frame, app: &App) {
     if app.paused {
         render_pause_indicator(frame, area);
     }
+
+    // Render command prompt if open
+    if let Some(input) = &app.command_input {
+        render_command_prompt(frame, input, area);
+    }
+
+    // Recolor everything drawn for the chosen theme
+    app.theme.apply(frame.buffer_mut());
 }
 
-fn render_header(frame: &mut Frame, app: &App, area: Rect) {
-    let header_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(25), // Clock
-            Constraint::Percentage(50), // Status/Title
-            Constraint::Percentage(25), // Countdown
-        ])
-        .split(area);
+/// Screen area of every visible panel; a zoomed panel fills the screen
+pub fn panel_areas(area: Rect, layout: &DashboardLayout, zoomed: Option<Panel>) -> Vec<(Panel, Rect)> {
+    match zoomed {
+        Some(panel) => vec![(panel, area)],
+        None => layout.areas(area),
+    }
+}
+
+fn render_panel(frame: &mut Frame, app: &App, panel: Panel, area: Rect) {
+    match panel {
+        Panel::Clock => render_clock(frame, &app.clock_state, &app.fonts, area),
+        Panel::Analog => render_analog_clock(frame, &app.clock_state, area),
+        Panel::Title => render_title(frame, area),
+        Panel::Countdown => render_countdown(frame, &app.timers_state, &app.fonts, area),
+        Panel::Timers => render_timers(frame, &app.timers_state, area),
+        Panel::Matrix => render_matrix_rain(frame, &app.matrix_state, area),
+        Panel::Source => render_source_code(frame, &app.source_state, area),
+        Panel::Map => match app.map_state.projection {
+            Projection::Flat => render_world_map(frame, &app.map_state, area),
+            Projection::Globe => render_globe(frame, &app.globe_state, &app.map_state, area),
+        },
+        Panel::Cpu => render_cpu_gauge(frame, &app.cpu_state, area),
+        Panel::Memory => render_memory_gauge(frame, &app.memory_state, area),
+        Panel::Network => render_network_monitor(frame, &app.network_state, area),
+        Panel::Sensors => render_sensors(frame, &app.sensors_state, area),
+        Panel::Disks => render_disk_monitor(frame, &app.disk_state, area),
+        Panel::Processes => render_process_table(frame, &app.process_state, area),
+        Panel::Logs => render_fake_logs(frame, &app.logs_state, area),
+        Panel::HexDump => render_hex_dump(frame, &app.hex_state, area),
+        Panel::Progress => render_progress_bars(frame, &app.progress_state, area),
+    }
+}
 
-    render_clock(frame, &app.clock_state, header_chunks[0]);
-    render_title(frame, header_chunks[1]);
-    render_countdown(frame, &app.countdown_state, header_chunks[2]);
+/// Recolor the border cells of a panel, leaving its title text alone
+fn highlight_border(buf: &mut Buffer, area: Rect, color: Color) {
+    let area = area.intersection(buf.area);
+    if area.width < 2 || area.height < 2 {
+        return;
+    }
+
+    let is_border = |symbol: &str| symbol.chars().all(|c| ('\u{2500}'..='\u{257f}').contains(&c));
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+    let edges = (area.left()..=right)
+        .flat_map(|x| [(x, area.top()), (x, bottom)])
+        .chain((area.top()..=bottom).flat_map(|y| [(area.left(), y), (right, y)]));
+
+    for (x, y) in edges {
+        let cell = &mut buf[(x, y)];
+        if is_border(cell.symbol()) {
+            cell.set_fg(color);
+        }
+    }
 }
 
 fn render_title(frame: &mut Frame, area: Rect) {
@@ -105,62 +161,6 @@ fn render_title(frame: &mut Frame, area: Rect) {
     frame.render_widget(title, area);
 }
 
-fn render_main_content(frame: &mut Frame, app: &App, area: Rect) {
-    // Split into three columns
-    let columns = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(25), // Left: Matrix + Source
-            Constraint::Percentage(45), // Center: World Map
-            Constraint::Percentage(30), // Right: System monitors + Logs
-        ])
-        .split(area);
-
-    // Left column: Matrix Rain (top) + Source Code (bottom)
-    let left_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage(50),
-            Constraint::Percentage(50),
-        ])
-        .split(columns[0]);
-
-    render_matrix_rain(frame, &app.matrix_state, left_chunks[0]);
-    render_source_code(frame, &app.source_state, left_chunks[1]);
-
-    // Center: World Map
-    render_world_map(frame, &app.map_state, columns[1]);
-
-    // Right column: System monitors (top) + Logs (bottom)
-    let right_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(4),  // CPU
-            Constraint::Length(4),  // Memory
-            Constraint::Length(6),  // Network
-            Constraint::Min(5),     // Logs
-        ])
-        .split(columns[2]);
-
-    render_cpu_gauge(frame, &app.cpu_state, right_chunks[0]);
-    render_memory_gauge(frame, &app.memory_state, right_chunks[1]);
-    render_network_monitor(frame, &app.network_state, right_chunks[2]);
-    render_fake_logs(frame, &app.logs_state, right_chunks[3]);
-}
-
-fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
-    let footer_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(60), // Hex dump
-            Constraint::Percentage(40), // Progress bars
-        ])
-        .split(area);
-
-    render_hex_dump(frame, &app.hex_state, footer_chunks[0]);
-    render_progress_bars(frame, &app.progress_state, footer_chunks[1]);
-}
-
 fn render_size_warning(frame: &mut Frame, area: Rect) {
     let warning = Paragraph::new(vec![
         Line::from(""),
@@ -189,7 +189,7 @@ fn render_size_warning(frame: &mut Frame, area: Rect) {
 }
 
 fn render_help_overlay(frame: &mut Frame, area: Rect) {
-    let help_area = centered_rect(50, 60, area);
+    let help_area = centered_rect(50, 90, area);
 
     frame.render_widget(Clear, help_area);
 
@@ -215,6 +215,50 @@ fn render_help_overlay(frame: &mut Frame, area: Rect) {
             Span::styled("  r        ", Style::default().fg(NEON_MAGENTA)),
             Span::styled("Reset countdown", Style::default().fg(TEXT_PRIMARY)),
         ]),
+        Line::from(vec![
+            Span::styled("  H        ", Style::default().fg(NEON_MAGENTA)),
+            Span::styled("Hold/resume countdown", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  ←↑↓→ s   ", Style::default().fg(NEON_MAGENTA)),
+            Span::styled("Timers: select / start", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  m        ", Style::default().fg(NEON_MAGENTA)),
+            Span::styled("Reveal matrix message", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  :        ", Style::default().fg(NEON_MAGENTA)),
+            Span::styled("Command prompt", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  TAB      ", Style::default().fg(NEON_MAGENTA)),
+            Span::styled("Focus next panel", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  ENTER    ", Style::default().fg(NEON_MAGENTA)),
+            Span::styled("Zoom focused panel", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  L        ", Style::default().fg(NEON_MAGENTA)),
+            Span::styled("Next layout", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  [ / ]    ", Style::default().fg(NEON_MAGENTA)),
+            Span::styled("Scenario: previous / next cue", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  ←↑↓→ i o ", Style::default().fg(NEON_MAGENTA)),
+            Span::styled("Map: pan / zoom", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  0-9 / t  ", Style::default().fg(NEON_MAGENTA)),
+            Span::styled("Map: region / tour", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  g        ", Style::default().fg(NEON_MAGENTA)),
+            Span::styled("Map: flat / globe", Style::default().fg(TEXT_PRIMARY)),
+        ]),
         Line::from(vec![
             Span::styled("  ? / h    ", Style::default().fg(NEON_MAGENTA)),
             Span::styled("Toggle help", Style::default().fg(TEXT_PRIMARY)),
@@ -240,6 +284,33 @@ fn render_help_overlay(frame: &mut Frame, area: Rect) {
     frame.render_widget(help, help_area);
 }
 
+fn render_alert_banner(frame: &mut Frame, name: &str, message: &str, area: Rect) {
+    frame.render_widget(Clear, area);
+
+    // Centre the text vertically inside the border
+    let top = area.height.saturating_sub(2 + 6) / 2;
+    let mut text = vec![Line::from(""); top as usize];
+    text.extend([
+        Line::from(Span::styled("⚠  ALERT  ⚠", Style::default().fg(NEON_RED).bold())),
+        Line::from(""),
+        Line::from(Span::styled(name.to_uppercase(), Style::default().fg(TEXT_PRIMARY).bold())),
+        Line::from(Span::styled(message.to_string(), Style::default().fg(NEON_ORANGE))),
+        Line::from(""),
+        Line::from(Span::styled("Press any key to dismiss", Style::default().fg(TEXT_DIM))),
+    ]);
+
+    let banner = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Double)
+                .border_style(Style::default().fg(NEON_RED)),
+        );
+
+    frame.render_widget(banner, area);
+}
+
 fn render_pause_indicator(frame: &mut Frame, area: Rect) {
     let pause_area = Rect {
         x: area.width.saturating_sub(12),
@@ -256,6 +327,27 @@ fn render_pause_indicator(frame: &mut Frame, area: Rect) {
     frame.render_widget(pause, pause_area);
 }
 
+fn render_command_prompt(frame: &mut Frame, input: &str, area: Rect) {
+    let width = (area.width * 3 / 5).max(40).min(area.width);
+    let prompt_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + area.height.saturating_sub(4),
+        width,
+        height: 3.min(area.height),
+    };
+
+    frame.render_widget(Clear, prompt_area);
+
+    let prompt = Paragraph::new(Line::from(vec![
+        Span::styled(":", Style::default().fg(NEON_MAGENTA).bold()),
+        Span::styled(input, Style::default().fg(TEXT_HIGHLIGHT)),
+        Span::styled("█", Style::default().fg(NEON_CYAN)),
+    ]))
+    .block(neon_block("COMMAND").border_style(Style::default().fg(BORDER_ACTIVE)));
+
+    frame.render_widget(prompt, prompt_area);
+}
+
 /// Helper function to create a centered rect
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -1,3 +1,4 @@
+pub mod dashboard;
 pub mod layout;
 pub mod theme;
 
@@ -1,4 +1,6 @@
-use ratatui::style::Color;
+use ratatui::{buffer::Buffer, style::Color};
+
+use crate::config::AccentColor;
 
 // Primary accent colors - Cyberpunk Neon
 pub const NEON_CYAN: Color = Color::Rgb(0, 255, 255);
@@ -11,6 +13,7 @@ pub const NEON_RED: Color = Color::Rgb(255, 50, 50);
 
 // Background colors
 pub const DARK_BG: Color = Color::Rgb(10, 10, 20);
+#[allow(dead_code)]
 pub const PANEL_BG: Color = Color::Rgb(15, 15, 30);
 pub const BORDER_DIM: Color = Color::Rgb(40, 40, 60);
 pub const BORDER_ACTIVE: Color = Color::Rgb(0, 200, 255);
@@ -24,6 +27,7 @@ pub const TEXT_HIGHLIGHT: Color = Color::Rgb(255, 255, 255);
 pub const STATUS_SUCCESS: Color = Color::Rgb(0, 255, 100);
 pub const STATUS_WARNING: Color = Color::Rgb(255, 200, 0);
 pub const STATUS_ERROR: Color = Color::Rgb(255, 50, 50);
+#[allow(dead_code)]
 pub const STATUS_INFO: Color = Color::Rgb(0, 200, 255);
 
 // Matrix Rain colors
@@ -39,6 +43,8 @@ pub const MAP_OUTLINE: Color = Color::Rgb(30, 80, 30);
 pub const MAP_NODE_IDLE: Color = Color::Rgb(0, 100, 100);
 pub const MAP_NODE_ACTIVE: Color = Color::Rgb(0, 255, 255);
 pub const MAP_CONNECTION: Color = Color::Rgb(0, 200, 200);
+pub const MAP_NIGHT: Color = Color::Rgb(30, 30, 80);
+pub const MAP_TWILIGHT: Color = Color::Rgb(60, 50, 100);
 
 // Log level colors
 pub const LOG_INFO: Color = Color::Rgb(0, 200, 255);
@@ -66,9 +72,98 @@ pub fn gauge_color(percentage: f64) -> Color {
     }
 }
 
+// Color for a temperature relative to its critical value
+pub fn temperature_color(celsius: f64, critical: f64) -> Color {
+    let ratio = celsius / critical.max(1.0);
+    if ratio < 0.7 {
+        NEON_CYAN
+    } else if ratio < 0.9 {
+        NEON_YELLOW
+    } else {
+        NEON_RED
+    }
+}
+
+// Palette color for a configured accent
+pub fn accent_color(accent: AccentColor) -> Color {
+    match accent {
+        AccentColor::Cyan => NEON_CYAN,
+        AccentColor::Magenta => NEON_MAGENTA,
+        AccentColor::Green => NEON_GREEN,
+        AccentColor::Orange => NEON_ORANGE,
+        AccentColor::Purple => NEON_PURPLE,
+        AccentColor::Yellow => NEON_YELLOW,
+        AccentColor::Red => NEON_RED,
+    }
+}
+
 // Progress bar gradient
+#[allow(dead_code)]
 pub fn progress_gradient(progress: f64) -> Color {
     let r = (255.0 * (1.0 - progress)) as u8;
     let g = (255.0 * progress) as u8;
     Color::Rgb(r, g, 128)
 }
+
+/// Color schemes a scene can switch to. Every scheme but the neon one tints
+/// the finished frame in a single hue, keeping each color's brightness.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Theme {
+    #[default]
+    Neon,
+    Amber,
+    Green,
+    Red,
+    Blue,
+    Mono,
+}
+
+impl Theme {
+    const ALL: [Theme; 6] = [Theme::Neon, Theme::Amber, Theme::Green, Theme::Red, Theme::Blue, Theme::Mono];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Neon => "neon",
+            Theme::Amber => "amber",
+            Theme::Green => "green",
+            Theme::Red => "red",
+            Theme::Blue => "blue",
+            Theme::Mono => "mono",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Theme> {
+        Self::ALL.into_iter().find(|theme| theme.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The scheme's hue at full brightness, or `None` for the neon palette
+    fn hue(self) -> Option<(f64, f64, f64)> {
+        match self {
+            Theme::Neon => None,
+            Theme::Amber => Some((255.0, 176.0, 0.0)),
+            Theme::Green => Some((51.0, 255.0, 102.0)),
+            Theme::Red => Some((255.0, 40.0, 40.0)),
+            Theme::Blue => Some((60.0, 160.0, 255.0)),
+            Theme::Mono => Some((230.0, 230.0, 230.0)),
+        }
+    }
+
+    /// Tint every cell of a drawn frame
+    pub fn apply(self, buf: &mut Buffer) {
+        let Some((r, g, b)) = self.hue() else {
+            return;
+        };
+        let tint = |color: Color| match color {
+            Color::Rgb(red, green, blue) => {
+                let level = red.max(green).max(blue) as f64 / 255.0;
+                Color::Rgb((r * level) as u8, (g * level) as u8, (b * level) as u8)
+            }
+            other => other,
+        };
+
+        for cell in &mut buf.content {
+            cell.fg = tint(cell.fg);
+            cell.bg = tint(cell.bg);
+        }
+    }
+}
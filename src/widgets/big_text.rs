@@ -0,0 +1,345 @@
+use ratatui::prelude::*;
+use std::{cmp::Reverse, collections::HashMap, fs, io, path::Path};
+
+use crate::config::FontConfig;
+
+/// Width of a space in the built-in fonts
+const SPACE_WIDTH: usize = 2;
+
+/// Seven-segment masks for 0-9, bits a..g = top, upper right, lower right,
+/// bottom, lower left, upper left, middle
+const SEGMENTS: [u8; 10] = [
+    0b0111111, 0b0000110, 0b1011011, 0b1001111, 0b1100110,
+    0b1101101, 0b1111101, 0b0000111, 0b1111111, 0b1101111,
+];
+
+/// A font of big characters, each a block of rows of equal width
+#[derive(Clone, Debug)]
+pub struct BigFont {
+    pub name: String,
+    pub height: usize,
+    glyphs: HashMap<char, Vec<String>>,
+}
+
+impl BigFont {
+    /// The 3-row half-block font
+    pub fn block3() -> Self {
+        Self::from_table("block3", &[
+            ('0', ["█▀█", "█ █", "▀▀▀"]),
+            ('1', ["▀█ ", " █ ", "▀▀▀"]),
+            ('2', ["▀▀█", "█▀▀", "▀▀▀"]),
+            ('3', ["▀▀█", " ▀█", "▀▀▀"]),
+            ('4', ["█ █", "▀▀█", "  ▀"]),
+            ('5', ["█▀▀", "▀▀█", "▀▀▀"]),
+            ('6', ["█▀▀", "█▀█", "▀▀▀"]),
+            ('7', ["▀▀█", "  █", "  ▀"]),
+            ('8', ["█▀█", "█▀█", "▀▀▀"]),
+            ('9', ["█▀█", "▀▀█", "▀▀▀"]),
+            (':', ["▄", " ", "▀"]),
+            ('.', [" ", " ", "▀"]),
+            ('d', ["  █", "█▀█", "▀▀▀"]),
+            ('T', ["▀█▀", " █ ", " ▀ "]),
+            ('-', ["   ", "▀▀▀", "   "]),
+            ('+', [" ▄ ", "▀█▀", "   "]),
+        ])
+    }
+
+    /// The 5-row full-block font
+    pub fn block5() -> Self {
+        Self::from_table("block5", &[
+            ('0', ["███", "█ █", "█ █", "█ █", "███"]),
+            ('1', [" █ ", "██ ", " █ ", " █ ", "███"]),
+            ('2', ["███", "  █", "███", "█  ", "███"]),
+            ('3', ["███", "  █", " ██", "  █", "███"]),
+            ('4', ["█ █", "█ █", "███", "  █", "  █"]),
+            ('5', ["███", "█  ", "███", "  █", "███"]),
+            ('6', ["███", "█  ", "███", "█ █", "███"]),
+            ('7', ["███", "  █", "  █", "  █", "  █"]),
+            ('8', ["███", "█ █", "███", "█ █", "███"]),
+            ('9', ["███", "█ █", "███", "  █", "███"]),
+            (':', [" ", "█", " ", "█", " "]),
+            ('.', [" ", " ", " ", " ", "█"]),
+            ('d', ["  █", "  █", "███", "█ █", "███"]),
+            ('T', ["███", " █ ", " █ ", " █ ", " █ "]),
+            ('-', ["   ", "   ", "███", "   ", "   "]),
+            ('+', ["   ", " █ ", "███", " █ ", "   "]),
+        ])
+    }
+
+    /// The 7-row seven-segment display font
+    pub fn segment7() -> Self {
+        let mut glyphs: HashMap<char, Vec<String>> = HashMap::new();
+        for (digit, &mask) in SEGMENTS.iter().enumerate() {
+            glyphs.insert(char::from(b'0' + digit as u8), seven_segment(mask));
+        }
+        // Letters and signs the way a segment display shows them
+        glyphs.insert('d', seven_segment(0b1011110));
+        glyphs.insert('T', seven_segment(0b1111000));
+        glyphs.insert('-', seven_segment(0b1000000));
+        glyphs.insert('+', vec![
+            "     ".into(), "     ".into(), "  ┃  ".into(), " ━╋━ ".into(),
+            "  ┃  ".into(), "     ".into(), "     ".into(),
+        ]);
+        let dots = |rows: &[usize]| (0..7).map(|row| if rows.contains(&row) { "▪" } else { " " }.to_string()).collect();
+        glyphs.insert(':', dots(&[2, 4]));
+        glyphs.insert('.', dots(&[6]));
+        glyphs.insert(' ', vec![" ".repeat(SPACE_WIDTH); 7]);
+
+        Self {
+            name: "segment7".to_string(),
+            height: 7,
+            glyphs,
+        }
+    }
+
+    fn from_table<const H: usize>(name: &str, table: &[(char, [&str; H])]) -> Self {
+        let mut glyphs: HashMap<char, Vec<String>> = table
+            .iter()
+            .map(|(ch, rows)| (*ch, rows.iter().map(|row| row.to_string()).collect()))
+            .collect();
+        glyphs.insert(' ', vec![" ".repeat(SPACE_WIDTH); H]);
+
+        Self {
+            name: name.to_string(),
+            height: H,
+            glyphs,
+        }
+    }
+
+    /// Load a FIGlet font; it is named after the file
+    pub fn from_figlet_file(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        let name = path.file_stem().map_or("figlet".into(), |stem| stem.to_string_lossy().into_owned());
+
+        Self::parse_figlet(&name, &text).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display()))
+        })
+    }
+
+    /// Parse a FIGlet `.flf` font: a header, comment lines, then the
+    /// printable ASCII characters, each `height` lines ending in an end mark.
+    /// Characters are set at full width, without smushing.
+    pub fn parse_figlet(name: &str, text: &str) -> Result<Self, String> {
+        let mut lines = text.lines();
+        let header = lines.next().ok_or("empty font file")?;
+        let params = header.strip_prefix("flf2a").ok_or("not a FIGlet font (missing flf2a header)")?;
+        let hardblank = params.chars().next().ok_or("missing hard blank in header")?;
+        let fields: Vec<&str> = params[hardblank.len_utf8()..].split_whitespace().collect();
+        let number = |index: usize, what: &str| -> Result<usize, String> {
+            fields
+                .get(index)
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(|| format!("invalid {what} in header"))
+        };
+        let height = number(0, "height")?;
+        let comment_lines = number(4, "comment line count")?;
+        if height == 0 {
+            return Err("font height is zero".to_string());
+        }
+
+        let mut lines = lines.skip(comment_lines);
+        let mut glyphs = HashMap::new();
+        for code in 32u8..=126 {
+            let mut rows = Vec::with_capacity(height);
+            for _ in 0..height {
+                let line = lines.next().ok_or_else(|| format!("font ends before character {code}"))?;
+                let line = line.trim_end_matches(['\r', '\n']);
+                let end_mark = line.chars().last().unwrap_or('@');
+                rows.push(line.trim_end_matches(end_mark).replace(hardblank, " "));
+            }
+            let width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+            let rows = rows.into_iter().map(|row| format!("{row:<width$}")).collect();
+            glyphs.insert(char::from(code), rows);
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            height,
+            glyphs,
+        })
+    }
+
+    fn glyph(&self, ch: char) -> Option<&Vec<String>> {
+        self.glyphs.get(&ch)
+    }
+
+    /// Can every character of the text be drawn?
+    pub fn supports(&self, text: &str) -> bool {
+        text.chars().all(|ch| self.glyphs.contains_key(&ch))
+    }
+
+    /// Width of the text in columns, with a column between characters
+    pub fn width(&self, text: &str) -> usize {
+        let glyphs = text.chars().filter_map(|ch| self.glyph(ch));
+        let (count, width) = glyphs.fold((0usize, 0), |(count, width), rows| {
+            (count + 1, width + rows.first().map_or(0, |row| row.chars().count()))
+        });
+        width + count.saturating_sub(1)
+    }
+
+    /// The text as rows of big characters; unknown characters are skipped
+    pub fn render(&self, text: &str) -> Vec<Line<'static>> {
+        (0..self.height)
+            .map(|row| {
+                let parts: Vec<&str> = text
+                    .chars()
+                    .filter_map(|ch| self.glyph(ch))
+                    .map(|rows| rows[row].as_str())
+                    .collect();
+                Line::from(parts.join(" "))
+            })
+            .collect()
+    }
+}
+
+/// Rows of a seven-segment digit from its segment mask
+fn seven_segment(mask: u8) -> Vec<String> {
+    let on = |segment: u8| mask & (1 << segment) != 0;
+    let horizontal = |segment| if on(segment) { " ━━━ " } else { "     " }.to_string();
+    let vertical = |left, right| {
+        let side = |segment| if on(segment) { '┃' } else { ' ' };
+        format!("{}   {}", side(left), side(right))
+    };
+
+    vec![
+        horizontal(0),
+        vertical(5, 1),
+        vertical(5, 1),
+        horizontal(6),
+        vertical(4, 2),
+        vertical(4, 2),
+        horizontal(3),
+    ]
+}
+
+/// The fonts available for big digits
+#[derive(Clone, Debug)]
+pub struct FontSet {
+    /// Tallest first
+    fonts: Vec<BigFont>,
+    /// Font to use instead of the biggest that fits
+    preferred: Option<String>,
+}
+
+impl FontSet {
+    pub fn new() -> Self {
+        Self::with_fonts(Vec::new(), None)
+    }
+
+    /// The built-in fonts, the configured FIGlet fonts and the font choice
+    pub fn with_config(config: &FontConfig) -> io::Result<Self> {
+        let figlet = config
+            .figlet
+            .iter()
+            .map(|path| BigFont::from_figlet_file(path))
+            .collect::<io::Result<Vec<_>>>()?;
+        let preferred = match config.name.as_str() {
+            "auto" => None,
+            name => Some(name.to_string()),
+        };
+
+        let set = Self::with_fonts(figlet, preferred);
+        if let Some(name) = &set.preferred {
+            if !set.fonts.iter().any(|font| &font.name == name) {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("unknown font '{name}'")));
+            }
+        }
+        Ok(set)
+    }
+
+    fn with_fonts(extra: Vec<BigFont>, preferred: Option<String>) -> Self {
+        // Loaded fonts win over built-in ones of the same height
+        let mut fonts = extra;
+        fonts.extend([BigFont::segment7(), BigFont::block5(), BigFont::block3()]);
+        fonts.sort_by_key(|font| Reverse(font.height));
+        Self { fonts, preferred }
+    }
+
+    /// The preferred font if it fits, otherwise the biggest that does
+    pub fn fit(&self, text: &str, width: u16, height: u16) -> Option<&BigFont> {
+        let fits = |font: &&BigFont| {
+            font.supports(text) && font.height <= height as usize && font.width(text) <= width as usize
+        };
+
+        let preferred = self
+            .preferred
+            .as_ref()
+            .and_then(|name| self.fonts.iter().find(|font| &font.name == name));
+        match preferred {
+            // Fall back to smaller fonts only
+            Some(font) => Some(font).filter(fits).or_else(|| {
+                self.fonts.iter().filter(|other| other.height < font.height).find(fits)
+            }),
+            None => self.fonts.iter().find(fits),
+        }
+    }
+}
+
+impl Default for FontSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A two-row font: each character drawn as itself over a bar, `1` wider
+    /// than the rest and `0` with a hard blank
+    fn font(header: &str, last: u8) -> String {
+        let mut text = format!("{header}\ncomment\n");
+        for code in 32..=last {
+            let (top, bottom) = match char::from(code) {
+                '1' => ("/1".to_string(), "_1"),
+                '0' => ("0$".to_string(), "--"),
+                ch => (ch.to_string(), "-"),
+            };
+            text.push_str(&format!("{top}@\n{bottom}@@\n"));
+        }
+        text
+    }
+
+    #[test]
+    fn parses_a_figlet_font() {
+        let font = BigFont::parse_figlet("tiny", &font("flf2a$ 2 1 8 0 1", 126)).unwrap();
+        assert_eq!((font.name.as_str(), font.height), ("tiny", 2));
+        assert!(font.supports("A1 ~"));
+        assert!(!font.supports("é"));
+
+        assert_eq!(font.glyph('A').unwrap(), &["A", "-"]);
+        assert_eq!(font.glyph('1').unwrap(), &["/1", "_1"]);
+        // Hard blanks become spaces
+        assert_eq!(font.glyph('0').unwrap(), &["0 ", "--"]);
+        assert_eq!(font.width("A1"), 4);
+
+        let rows: Vec<String> = font.render("10").iter().map(|line| line.to_string()).collect();
+        assert_eq!(rows, ["/1 0 ", "_1 --"]);
+    }
+
+    #[test]
+    fn pads_rows_to_the_widest_one() {
+        let text = font("flf2a$ 2 1 8 0 1", 126).replace("A@\n-@@\n", "AAA@\n-@@\n");
+        let font = BigFont::parse_figlet("tiny", &text).unwrap();
+        assert_eq!(font.glyph('A').unwrap(), &["AAA", "-  "]);
+    }
+
+    #[test]
+    fn rejects_broken_fonts() {
+        let cases = [
+            ("", "empty font file"),
+            ("flf2", "not a FIGlet font (missing flf2a header)"),
+            ("flf2a", "missing hard blank in header"),
+            ("flf2a$ x 1 8 0 1", "invalid height in header"),
+            ("flf2a$ 2 1 8 0", "invalid comment line count in header"),
+            ("flf2a$ 0 1 8 0 1", "font height is zero"),
+        ];
+        for (header, error) in cases {
+            assert_eq!(BigFont::parse_figlet("bad", header).err().unwrap(), error, "{header}");
+        }
+
+        let truncated = font("flf2a$ 2 1 8 0 1", 64);
+        assert_eq!(BigFont::parse_figlet("bad", &truncated).err().unwrap(), "font ends before character 65");
+    }
+}
@@ -1,41 +1,243 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, BorderType, Paragraph},
+    widgets::{
+        canvas::{Canvas, Circle, Line as CanvasLine},
+        Block, Borders, BorderType, Paragraph,
+    },
 };
-use chrono::Local;
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, FixedOffset, Local, TimeZone, Timelike, Utc,
+};
+use chrono_tz::Tz;
+use std::{f64::consts::TAU, io};
 
+use crate::config::{ClockConfig, ClockShow, ZoneConfig};
 use crate::ui::theme::*;
+use crate::widgets::big_text::FontSet;
+use crate::widgets::world_map::MapNode;
+use crate::TICKS_PER_SECOND;
 
-pub struct ClockState {
+/// Narrowest column per zone when showing them side by side
+const MIN_COLUMN_WIDTH: u16 = 12;
+/// Canvas margin around the unit clock face
+const FACE_MARGIN: f64 = 1.1;
+/// Inner height from which the analog face gets numerals and a readout
+const BIG_FACE_HEIGHT: u16 = 16;
+
+/// Where a clock zone takes its time from
+#[derive(Clone, Copy, Debug)]
+pub enum ZoneTime {
+    Local,
+    Zone(Tz),
+    /// Mean solar time at a longitude, for map nodes without a zone
+    Solar(f64),
+}
+
+impl ZoneTime {
+    fn format(self, now: DateTime<Utc>, format: &str) -> String {
+        match self {
+            ZoneTime::Local => now.with_timezone(&Local).format(format).to_string(),
+            ZoneTime::Zone(tz) => now.with_timezone(&tz).format(format).to_string(),
+            ZoneTime::Solar(lon) => now.with_timezone(&solar_offset(lon)).format(format).to_string(),
+        }
+    }
+
+    /// Seconds since midnight, with milliseconds, for the analog hands
+    fn day_seconds(self, now: DateTime<Utc>) -> f64 {
+        fn seconds<T: TimeZone>(time: DateTime<T>) -> f64 {
+            time.num_seconds_from_midnight() as f64 + (time.nanosecond() / 1_000_000).min(999) as f64 / 1000.0
+        }
+
+        match self {
+            ZoneTime::Local => seconds(now.with_timezone(&Local)),
+            ZoneTime::Zone(tz) => seconds(now.with_timezone(&tz)),
+            ZoneTime::Solar(lon) => seconds(now.with_timezone(&solar_offset(lon))),
+        }
+    }
+}
+
+/// Whole-hour offset nearest to the mean solar time at a longitude
+fn solar_offset(lon: f64) -> FixedOffset {
+    let hours = (lon.clamp(-180.0, 180.0) / 15.0).round() as i32;
+    FixedOffset::east_opt(hours * 3600).expect("offset is within a day")
+}
+
+/// One time zone on the clock with its formatted time
+pub struct ClockZone {
+    pub label: String,
+    time: ZoneTime,
     pub time_str: String,
     pub date_str: String,
     pub millis: String,
+    /// Seconds since midnight, for the analog face
+    pub day_seconds: f64,
+}
+
+impl ClockZone {
+    fn new(label: String, time: ZoneTime) -> Self {
+        Self {
+            label,
+            time,
+            time_str: String::new(),
+            date_str: String::new(),
+            millis: String::new(),
+            day_seconds: 0.0,
+        }
+    }
+
+    /// The zone of a world map node
+    fn for_node(node: &MapNode) -> Self {
+        let time = match node.tz {
+            Some(tz) => ZoneTime::Zone(tz),
+            None => ZoneTime::Solar(node.lon),
+        };
+        Self::new(node.name.to_uppercase(), time)
+    }
+
+    fn from_config(config: &ZoneConfig, nodes: &[MapNode]) -> Result<Self, String> {
+        let mut zone = match (&config.zone, &config.node) {
+            (Some(zone), None) if zone.eq_ignore_ascii_case("local") => Self::new("LOCAL".to_string(), ZoneTime::Local),
+            (Some(zone), None) => {
+                let tz: Tz = zone.parse().map_err(|_| format!("unknown time zone '{zone}'"))?;
+                // "America/New_York" is labelled "NEW YORK"
+                let city = zone.rsplit('/').next().unwrap_or(zone);
+                Self::new(city.replace('_', " ").to_uppercase(), ZoneTime::Zone(tz))
+            }
+            (None, Some(name)) => nodes
+                .iter()
+                .find(|node| node.name.eq_ignore_ascii_case(name) || node.label.eq_ignore_ascii_case(name))
+                .map(Self::for_node)
+                .ok_or_else(|| format!("unknown map node '{name}'"))?,
+            _ => return Err("a zone needs either `zone` or `node`".to_string()),
+        };
+
+        if let Some(label) = &config.label {
+            zone.label = label.clone();
+        }
+        Ok(zone)
+    }
+
+    fn update(&mut self, now: DateTime<Utc>, time_format: &str) {
+        self.time_str = self.time.format(now, time_format);
+        self.date_str = self.time.format(now, "%Y-%m-%d");
+        self.millis = self.time.format(now, ".%3f");
+        self.day_seconds = self.time.day_seconds(now);
+    }
+}
+
+pub struct ClockState {
+    pub zones: Vec<ClockZone>,
+    /// Zone shown when cycling
+    pub selected: usize,
+    /// The selected map node's zone, shown instead while following the map
+    pub followed: Option<ClockZone>,
+    pub follow_map: bool,
+    pub show: ClockShow,
+    /// Move through the zones by itself
+    pub rotating: bool,
+    cycle_ticks: u64,
+    time_format: String,
+    /// Add milliseconds after the default 24-hour format
+    show_millis: bool,
+    /// Show the time in big digits when there is room
+    pub big_digits: bool,
+    now: DateTime<Utc>,
     tick_counter: u64,
 }
 
 impl ClockState {
     pub fn new() -> Self {
+        Self::with_config(&ClockConfig::default(), &[]).expect("default clock config is valid")
+    }
+
+    /// Zones given by map node are looked up in `nodes`
+    pub fn with_config(config: &ClockConfig, nodes: &[MapNode]) -> io::Result<Self> {
+        let mut zones = config
+            .zones
+            .iter()
+            .map(|zone| ClockZone::from_config(zone, nodes))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, format!("clock: {e}")))?;
+        if zones.is_empty() {
+            zones.push(ClockZone::new("LOCAL".to_string(), ZoneTime::Local));
+        }
+
+        // chrono panics when formatting with an invalid specifier, so check up front
+        if let Some(format) = &config.format {
+            if StrftimeItems::new(format).any(|item| item == Item::Error) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("clock: invalid time format '{format}'"),
+                ));
+            }
+        }
+
+        let default_format = if config.hour12 { "%I:%M:%S %p" } else { "%H:%M:%S" };
         let mut state = Self {
-            time_str: String::new(),
-            date_str: String::new(),
-            millis: String::new(),
+            zones,
+            selected: 0,
+            followed: None,
+            follow_map: config.follow_map,
+            show: config.show,
+            rotating: true,
+            cycle_ticks: ((config.cycle_secs.max(1.0) * TICKS_PER_SECOND) as u64).max(1),
+            time_format: config.format.clone().unwrap_or_else(|| default_format.to_string()),
+            show_millis: config.format.is_none() && !config.hour12,
+            big_digits: config.big_digits,
+            now: Utc::now(),
             tick_counter: 0,
         };
-        state.update_time();
-        state
+        state.update_time(Utc::now());
+        Ok(state)
     }
 
     pub fn tick(&mut self) {
         self.tick_counter += 1;
-        // Update time every tick for smooth millisecond display
-        self.update_time();
+
+        if self.rotating && self.show == ClockShow::Cycle && self.tick_counter.is_multiple_of(self.cycle_ticks) {
+            self.cycle(true);
+        }
     }
 
-    fn update_time(&mut self) {
-        let now = Local::now();
-        self.time_str = now.format("%H:%M:%S").to_string();
-        self.date_str = now.format("%Y-%m-%d").to_string();
-        self.millis = now.format(".%3f").to_string();
+    /// Show a new time; called every tick for smooth millisecond display
+    pub fn update_time(&mut self, now: DateTime<Utc>) {
+        self.now = now;
+        for zone in self.zones.iter_mut().chain(self.followed.as_mut()) {
+            zone.update(now, &self.time_format);
+        }
+    }
+
+    /// Show the time at the selected map node, if following the map
+    pub fn follow_node(&mut self, node: Option<&MapNode>) {
+        if !self.follow_map {
+            return;
+        }
+        match node {
+            Some(node) if self.followed.as_ref().is_some_and(|zone| zone.label == node.name.to_uppercase()) => {}
+            Some(node) => {
+                let mut zone = ClockZone::for_node(node);
+                zone.update(self.now, &self.time_format);
+                self.followed = Some(zone);
+            }
+            None => self.followed = None,
+        }
+    }
+
+    /// Show the next or previous zone
+    pub fn cycle(&mut self, forward: bool) {
+        let count = self.zones.len();
+        self.selected = if forward { (self.selected + 1) % count } else { (self.selected + count - 1) % count };
+    }
+
+    /// The zone on display when showing one at a time
+    pub fn shown(&self) -> &ClockZone {
+        self.followed.as_ref().unwrap_or(&self.zones[self.selected])
+    }
+
+    /// Only the local time, with no zone to name
+    fn is_plain(&self) -> bool {
+        self.followed.is_none() && self.zones.len() == 1 && matches!(self.zones[0].time, ZoneTime::Local)
     }
 }
 
@@ -45,14 +247,27 @@ impl Default for ClockState {
     }
 }
 
-pub fn render_clock(frame: &mut Frame, state: &ClockState, area: Rect) {
+/// The panel frame, naming the zone on display unless all are shown
+fn clock_block(state: &ClockState, title: &'static str, name_zone: bool) -> Block<'static> {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(BORDER_DIM))
-        .title(" SYSTEM TIME ")
+        .title(title)
         .title_style(Style::default().fg(NEON_CYAN).bold());
 
+    if !name_zone || state.is_plain() {
+        return block;
+    }
+    let label = format!(" {} ", state.shown().label);
+    block.title(Line::styled(label, Style::default().fg(NEON_MAGENTA).bold()).right_aligned())
+}
+
+pub fn render_clock(frame: &mut Frame, state: &ClockState, fonts: &FontSet, area: Rect) {
+    let title = if state.is_plain() { " SYSTEM TIME " } else { " WORLD CLOCK " };
+    let side_by_side = state.show == ClockShow::All && state.followed.is_none() && state.zones.len() > 1;
+    let block = clock_block(state, title, !side_by_side);
+
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -60,26 +275,156 @@ pub fn render_clock(frame: &mut Frame, state: &ClockState, area: Rect) {
         return;
     }
 
-    let blink = (state.tick_counter / 30) % 2 == 0;
+    let blink = (state.tick_counter / 30).is_multiple_of(2);
 
-    let time_line = Line::from(vec![
-        Span::styled(&state.time_str, Style::default().fg(TEXT_HIGHLIGHT).bold()),
-        Span::styled(
-            &state.millis,
-            Style::default().fg(if blink { NEON_CYAN } else { TEXT_DIM }),
-        ),
-    ]);
+    if side_by_side {
+        render_zones(frame, state, inner);
+        return;
+    }
 
+    let zone = state.shown();
     let date_line = Line::from(vec![
-        Span::styled(&state.date_str, Style::default().fg(TEXT_DIM)),
+        Span::styled(zone.date_str.as_str(), Style::default().fg(TEXT_DIM)),
         Span::styled("  ", Style::default()),
         Span::styled("●", Style::default().fg(if blink { NEON_GREEN } else { TEXT_DIM })),
         Span::styled(" SYNC", Style::default().fg(TEXT_DIM)),
     ]);
 
+    // Big digits without the milliseconds, with the date below if there is room
+    let big = state.big_digits.then(|| fonts.fit(&zone.time_str, inner.width, inner.height)).flatten();
+    if let Some(font) = big {
+        let mut lines = font.render(&zone.time_str);
+        if font.height < inner.height as usize {
+            lines.push(date_line);
+        }
+        let clock = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(TEXT_HIGHLIGHT));
+        frame.render_widget(clock, inner);
+        return;
+    }
+
+    let mut time_line = Line::from(Span::styled(zone.time_str.as_str(), Style::default().fg(TEXT_HIGHLIGHT).bold()));
+    if state.show_millis {
+        time_line.push_span(Span::styled(
+            zone.millis.as_str(),
+            Style::default().fg(if blink { NEON_CYAN } else { TEXT_DIM }),
+        ));
+    }
+
     let clock_text = vec![time_line, date_line];
 
     let clock = Paragraph::new(clock_text).alignment(Alignment::Center);
 
     frame.render_widget(clock, inner);
 }
+
+/// All zones in columns, or one per row when the columns would be too narrow
+fn render_zones(frame: &mut Frame, state: &ClockState, area: Rect) {
+    let count = state.zones.len() as u16;
+
+    if area.width / count < MIN_COLUMN_WIDTH {
+        let label_width = state.zones.iter().map(|zone| zone.label.chars().count()).max().unwrap_or(0);
+        for (zone, row) in state.zones.iter().zip(area.rows()) {
+            let line = Line::from(vec![
+                Span::styled(format!("{:<label_width$}  ", zone.label), Style::default().fg(NEON_MAGENTA)),
+                Span::styled(zone.time_str.as_str(), Style::default().fg(TEXT_HIGHLIGHT).bold()),
+            ]);
+            frame.render_widget(line, row);
+        }
+        return;
+    }
+
+    let columns = Layout::horizontal(vec![Constraint::Fill(1); count as usize]).split(area);
+    for (zone, &column) in state.zones.iter().zip(columns.iter()) {
+        let lines = vec![
+            Line::styled(zone.label.as_str(), Style::default().fg(NEON_MAGENTA).bold()),
+            Line::styled(zone.time_str.as_str(), Style::default().fg(TEXT_HIGHLIGHT).bold()),
+            Line::styled(zone.date_str.as_str(), Style::default().fg(TEXT_DIM)),
+        ];
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), column);
+    }
+}
+
+/// A clock face with hour, minute and sweeping second hands for the zone
+/// on display
+pub fn render_analog_clock(frame: &mut Frame, state: &ClockState, area: Rect) {
+    let block = clock_block(state, " CHRONOMETER ", true);
+    let inner = block.inner(area);
+    let zone = state.shown();
+
+    // Terminal cells are about twice as tall as wide; size the bounds so
+    // the face comes out round
+    let (width, height) = (inner.width.max(1) as f64, inner.height.max(1) as f64 * 2.0);
+    let (x_half, y_half) = if width >= height {
+        (FACE_MARGIN * width / height, FACE_MARGIN)
+    } else {
+        (FACE_MARGIN, FACE_MARGIN * height / width)
+    };
+    let big = inner.height >= BIG_FACE_HEIGHT;
+
+    // Hand angles clockwise from twelve, in turns
+    let seconds = zone.day_seconds;
+    let second_turn = seconds % 60.0 / 60.0;
+    let minute_turn = seconds % 3600.0 / 3600.0;
+    let hour_turn = seconds % 43200.0 / 43200.0;
+    let point = |turn: f64, radius: f64| {
+        let angle = turn * TAU;
+        (radius * angle.sin(), radius * angle.cos())
+    };
+    let hand = |turn: f64, length: f64, color: Color| {
+        let (x2, y2) = point(turn, length);
+        CanvasLine { x1: 0.0, y1: 0.0, x2, y2, color }
+    };
+
+    let canvas = Canvas::default()
+        .block(block)
+        .x_bounds([-x_half, x_half])
+        .y_bounds([-y_half, y_half])
+        .marker(symbols::Marker::Braille)
+        .paint(|ctx| {
+            ctx.draw(&Circle { x: 0.0, y: 0.0, radius: 1.0, color: BORDER_DIM });
+
+            // Tick marks: long at the quarters, shorter at the hours, and
+            // dots for the minutes on a big face
+            for minute in 0..60 {
+                let turn = minute as f64 / 60.0;
+                let (length, color) = match minute {
+                    m if m % 15 == 0 => (0.18, NEON_CYAN),
+                    m if m % 5 == 0 => (0.1, TEXT_PRIMARY),
+                    _ if big => (0.02, TEXT_DIM),
+                    _ => continue,
+                };
+                let (x1, y1) = point(turn, 0.95);
+                let (x2, y2) = point(turn, 0.95 - length);
+                ctx.draw(&CanvasLine { x1, y1, x2, y2, color });
+            }
+
+            if big {
+                for (hour, label) in [(0.0, "12"), (0.25, "3"), (0.5, "6"), (0.75, "9")] {
+                    let (x, y) = point(hour, 0.68);
+                    ctx.print(x, y, Span::styled(label, Style::default().fg(NEON_CYAN).bold()));
+                }
+            }
+            ctx.layer();
+
+            ctx.draw(&hand(hour_turn, 0.5, TEXT_HIGHLIGHT));
+            ctx.draw(&hand(minute_turn, 0.78, NEON_CYAN));
+            ctx.draw(&hand(second_turn, 0.88, NEON_MAGENTA));
+            // A short tail behind the second hand
+            ctx.draw(&hand(second_turn + 0.5, 0.15, NEON_MAGENTA));
+            ctx.draw(&Circle { x: 0.0, y: 0.0, radius: 0.03, color: NEON_MAGENTA });
+        });
+    frame.render_widget(canvas, area);
+
+    // Digital readout under the face when there is room
+    if big {
+        let readout = Rect { y: inner.bottom() - 1, height: 1, ..inner };
+        let line = Line::from(vec![
+            Span::styled(zone.time_str.as_str(), Style::default().fg(TEXT_HIGHLIGHT).bold()),
+            Span::styled("  ", Style::default()),
+            Span::styled(zone.date_str.as_str(), Style::default().fg(TEXT_DIM)),
+        ]);
+        frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), readout);
+    }
+}
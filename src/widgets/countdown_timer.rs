@@ -1,63 +1,287 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, BorderType, Paragraph},
 };
+use std::io;
 
+use crate::config::{CountdownConfig, CountdownMode, ExpiryConfig};
 use crate::ui::theme::*;
+use crate::widgets::big_text::FontSet;
+use crate::TICKS_PER_SECOND;
+
+/// Ticks each timer stays in the countdown panel when rotating (5 seconds)
+const ROTATE_TICKS: u64 = 5 * TICKS_PER_SECOND as u64;
+
+/// Longest count a timer takes: a hundred years
+const MAX_SECONDS: u64 = 100 * 365 * 86400;
 
 pub struct CountdownState {
+    pub name: Option<String>,
+    pub mode: CountdownMode,
+    /// Length of the count when there is no target
     pub initial_seconds: u64,
-    pub remaining_seconds: u64,
+    /// Absolute time to count to, if configured
+    target: Option<DateTime<Utc>>,
+    /// When the count reaches zero, not counting holds
+    zero: DateTime<Utc>,
+    /// When the count was last reset
+    started: DateTime<Utc>,
+    now: DateTime<Utc>,
+    /// Time spent on finished holds
+    held: Duration,
+    /// Start of the current hold
+    hold_start: Option<DateTime<Utc>>,
+    /// Show tenths of a second
+    pub tenths: bool,
+    pub warning_seconds: u64,
+    pub critical_seconds: u64,
+    pub color: Color,
+    /// Start counting on reset, or wait on hold
+    autostart: bool,
+    pub expiry: ExpiryConfig,
+    /// Has reaching zero been reported since the last reset?
+    zero_reported: bool,
     tick_counter: u64,
     flash_state: bool,
 }
 
 impl CountdownState {
     pub fn new(seconds: u64) -> Self {
-        Self {
-            initial_seconds: seconds,
-            remaining_seconds: seconds,
+        let config = CountdownConfig {
+            seconds,
+            ..CountdownConfig::default()
+        };
+        Self::with_config(&config, Utc::now()).expect("countdown without a target is valid")
+    }
+
+    pub fn with_config(config: &CountdownConfig, now: DateTime<Utc>) -> io::Result<Self> {
+        let name = config.name.as_deref().unwrap_or("countdown");
+        if config.seconds > MAX_SECONDS {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{name}: seconds must be at most {MAX_SECONDS} (a hundred years)"),
+            ));
+        }
+        let target = match &config.target {
+            Some(text) => Some(parse_target(text, config.timezone.as_deref(), now).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidInput, format!("{name}: {e}"))
+            })?),
+            None => None,
+        };
+
+        let mut state = Self {
+            name: config.name.clone(),
+            mode: config.mode,
+            initial_seconds: config.seconds,
+            target,
+            zero: now,
+            started: now,
+            now,
+            held: Duration::zero(),
+            hold_start: None,
+            tenths: config.tenths,
+            warning_seconds: config.warning,
+            critical_seconds: config.critical,
+            color: accent_color(config.color),
+            autostart: config.autostart,
+            expiry: config.expiry.clone(),
+            zero_reported: false,
             tick_counter: 0,
             flash_state: false,
-        }
+        };
+        state.reset();
+        Ok(state)
     }
 
     pub fn tick(&mut self) {
         self.tick_counter += 1;
 
         // Update flash state every 15 ticks
-        if self.tick_counter % 15 == 0 {
+        if self.tick_counter.is_multiple_of(15) {
             self.flash_state = !self.flash_state;
         }
+    }
 
-        // Decrement every 60 ticks (1 second at 60fps)
-        if self.tick_counter % 60 == 0 && self.remaining_seconds > 0 {
-            self.remaining_seconds -= 1;
-        }
+    /// Follow the wall clock; called every tick
+    pub fn update_time(&mut self, now: DateTime<Utc>) {
+        self.now = now;
     }
 
+    /// Start the count again from now; a target stays where it is
     pub fn reset(&mut self) {
-        self.remaining_seconds = self.initial_seconds;
+        self.zero = match (self.target, self.mode) {
+            (Some(target), _) => target,
+            (None, CountdownMode::Up) => self.now,
+            (None, _) => self.now + Duration::seconds(self.initial_seconds as i64),
+        };
+        self.started = self.now;
+        self.held = Duration::zero();
+        self.hold_start = if self.autostart { None } else { Some(self.now) };
         self.tick_counter = 0;
+        // A target already in the past is not reported again
+        self.zero_reported = self.mode == CountdownMode::Up || self.elapsed_millis() >= 0;
+    }
+
+    /// Has the count just reached zero? True once per reset
+    fn check_zero(&mut self) -> bool {
+        if self.zero_reported || self.elapsed_millis() < 0 {
+            return false;
+        }
+        self.zero_reported = true;
+        true
+    }
+
+    /// Stop the count while the clock keeps going
+    pub fn hold(&mut self) {
+        if self.hold_start.is_none() {
+            self.hold_start = Some(self.now);
+        }
+    }
+
+    /// Carry on counting from where the hold stopped it
+    pub fn resume(&mut self) {
+        if let Some(start) = self.hold_start.take() {
+            self.held += self.now - start;
+        }
+    }
+
+    pub fn toggle_hold(&mut self) {
+        if self.is_held() {
+            self.resume();
+        } else {
+            self.hold();
+        }
+    }
+
+    pub fn is_held(&self) -> bool {
+        self.hold_start.is_some()
+    }
+
+    /// Milliseconds since zero, negative before it, holds left out
+    fn elapsed_millis(&self) -> i64 {
+        let holding = self.hold_start.map_or(Duration::zero(), |start| self.now - start);
+        (self.now - self.zero - self.held - holding).num_milliseconds()
+    }
+
+    /// Is the count still before zero?
+    fn is_counting_down(&self) -> bool {
+        self.mode != CountdownMode::Up && self.elapsed_millis() < 0
+    }
+
+    /// Time shown, in milliseconds; a countdown stops at zero
+    fn shown_millis(&self) -> u64 {
+        let elapsed = self.elapsed_millis();
+        match self.mode {
+            CountdownMode::Down => (-elapsed).max(0) as u64,
+            CountdownMode::Up => elapsed.max(0) as u64,
+            CountdownMode::Mission => elapsed.unsigned_abs(),
+        }
+    }
+
+    /// Whole seconds left before zero, rounded up
+    pub fn remaining_seconds(&self) -> u64 {
+        if self.is_counting_down() {
+            (-self.elapsed_millis() as u64).div_ceil(1000)
+        } else {
+            0
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.mode == CountdownMode::Down && self.remaining_seconds() == 0
     }
 
     pub fn is_critical(&self) -> bool {
-        self.remaining_seconds <= 10
+        self.is_counting_down() && self.remaining_seconds() <= self.critical_seconds
     }
 
     pub fn is_warning(&self) -> bool {
-        self.remaining_seconds <= 60
+        self.is_counting_down() && self.remaining_seconds() <= self.warning_seconds
     }
 
+    /// The shown time, e.g. `04:59`, `1d 02:03:04`, `T-00:10.0`
     pub fn format_time(&self) -> String {
-        let hours = self.remaining_seconds / 3600;
-        let minutes = (self.remaining_seconds % 3600) / 60;
-        let seconds = self.remaining_seconds % 60;
+        // Counting down rounds up, so zero shows only once it is reached
+        let unit = if self.tenths { 100 } else { 1000 };
+        let millis = self.shown_millis();
+        let units = if self.is_counting_down() { millis.div_ceil(unit) } else { millis / unit };
+        let (total_seconds, tenths) = if self.tenths { (units / 10, Some(units % 10)) } else { (units, None) };
 
-        if hours > 0 {
+        let days = total_seconds / 86400;
+        let hours = (total_seconds % 86400) / 3600;
+        let minutes = (total_seconds % 3600) / 60;
+        let seconds = total_seconds % 60;
+
+        let mut time = if days > 0 {
+            format!("{}d {:02}:{:02}:{:02}", days, hours, minutes, seconds)
+        } else if hours > 0 {
             format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
         } else {
             format!("{:02}:{:02}", minutes, seconds)
+        };
+        if let Some(tenths) = tenths {
+            time.push_str(&format!(".{tenths}"));
+        }
+
+        if self.mode == CountdownMode::Mission {
+            let sign = if self.is_counting_down() { "T-" } else { "T+" };
+            time.insert_str(0, sign);
+        }
+        time
+    }
+
+    /// Share of the count left before zero, if it is counting down
+    pub fn fraction_left(&self) -> Option<f64> {
+        let total = (self.zero - self.started).num_milliseconds();
+        (self.is_counting_down() && total > 0).then(|| (-self.elapsed_millis()) as f64 / total as f64)
+    }
+
+    /// Mode label, e.g. "T-MINUS"
+    pub fn mode_label(&self) -> &'static str {
+        match self.mode {
+            CountdownMode::Down => "COUNTDOWN",
+            CountdownMode::Up => "STOPWATCH",
+            CountdownMode::Mission if self.is_counting_down() => "T-MINUS",
+            CountdownMode::Mission => "MISSION ELAPSED",
+        }
+    }
+
+    /// The timer's name, or its mode if unnamed
+    pub fn title(&self) -> &str {
+        self.name.as_deref().unwrap_or(self.mode_label())
+    }
+
+    /// Color for the current state, flashing when critical or expired
+    fn state_color(&self) -> Color {
+        if self.is_held() {
+            NEON_YELLOW
+        } else if self.is_expired() {
+            if self.flash_state { NEON_RED } else { DARK_BG }
+        } else if self.is_critical() {
+            if self.flash_state { NEON_RED } else { NEON_ORANGE }
+        } else if self.is_warning() {
+            NEON_ORANGE
+        } else {
+            self.color
+        }
+    }
+
+    /// Short status word and its color
+    fn status(&self) -> (&'static str, Color) {
+        if self.is_held() {
+            ("▌▌ HOLD", NEON_YELLOW)
+        } else if self.is_expired() {
+            ("█ EXPIRED █", NEON_RED)
+        } else if self.is_critical() {
+            ("⚠ CRITICAL", NEON_RED)
+        } else if self.is_warning() {
+            ("◆ WARNING", NEON_ORANGE)
+        } else if self.mode == CountdownMode::Mission && !self.is_counting_down() {
+            ("▲ LIFTOFF", NEON_GREEN)
+        } else {
+            ("◇ ACTIVE", NEON_GREEN)
         }
     }
 }
@@ -68,23 +292,198 @@ impl Default for CountdownState {
     }
 }
 
-pub fn render_countdown(frame: &mut Frame, state: &CountdownState, area: Rect) {
-    let color = if state.remaining_seconds == 0 {
-        if state.flash_state { NEON_RED } else { DARK_BG }
-    } else if state.is_critical() {
-        if state.flash_state { NEON_RED } else { NEON_ORANGE }
-    } else if state.is_warning() {
-        NEON_ORANGE
-    } else {
-        NEON_CYAN
+/// All countdown timers; the countdown panel shows one at a time
+pub struct TimersState {
+    pub timers: Vec<CountdownState>,
+    /// Timer in the countdown panel, and the one keys act on
+    pub selected: usize,
+    /// Move the countdown panel through the timers by itself
+    pub rotating: bool,
+    /// A key or command acted on the selected timer, so the panel stays on it
+    pinned: bool,
+    /// Timers that reached zero since the last `take_expired`
+    expired: Vec<usize>,
+    /// Timers a pause of the dashboard put on hold, to resume with it
+    paused: Vec<usize>,
+    tick_counter: u64,
+}
+
+impl TimersState {
+    pub fn new() -> Self {
+        Self::from_timers(vec![CountdownState::default()])
+    }
+
+    /// The given timers; there is always at least one
+    pub fn with_config(configs: &[CountdownConfig], now: DateTime<Utc>) -> io::Result<Self> {
+        let default = [CountdownConfig::default()];
+        let configs = if configs.is_empty() { &default[..] } else { configs };
+        let timers = configs
+            .iter()
+            .map(|config| CountdownState::with_config(config, now))
+            .collect::<io::Result<_>>()?;
+        Ok(Self::from_timers(timers))
+    }
+
+    fn from_timers(timers: Vec<CountdownState>) -> Self {
+        Self {
+            timers,
+            selected: 0,
+            rotating: true,
+            pinned: false,
+            expired: Vec::new(),
+            paused: Vec::new(),
+            tick_counter: 0,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.tick_counter += 1;
+        for timer in &mut self.timers {
+            timer.tick();
+        }
+
+        if self.rotating && !self.pinned && self.tick_counter.is_multiple_of(ROTATE_TICKS) {
+            self.cycle(true);
+        }
+    }
+
+    pub fn update_time(&mut self, now: DateTime<Utc>) {
+        for (index, timer) in self.timers.iter_mut().enumerate() {
+            timer.update_time(now);
+            if timer.check_zero() {
+                self.expired.push(index);
+            }
+        }
+    }
+
+    /// Hold every running timer while the dashboard is paused, so timers
+    /// on the wall clock stop just like those on a virtual one
+    pub fn pause(&mut self, now: DateTime<Utc>) {
+        self.update_time(now);
+        self.paused = (0..self.timers.len()).filter(|&index| !self.timers[index].is_held()).collect();
+        for &index in &self.paused {
+            self.timers[index].hold();
+        }
+    }
+
+    /// Resume the timers `pause` held
+    pub fn unpause(&mut self, now: DateTime<Utc>) {
+        self.update_time(now);
+        for index in std::mem::take(&mut self.paused) {
+            self.timers[index].resume();
+        }
+    }
+
+    /// Timers that reached zero since the last call
+    pub fn take_expired(&mut self) -> Vec<usize> {
+        std::mem::take(&mut self.expired)
+    }
+
+    /// Select the next or previous timer
+    pub fn cycle(&mut self, forward: bool) {
+        let count = self.timers.len();
+        self.selected = if forward { (self.selected + 1) % count } else { (self.selected + count - 1) % count };
+    }
+
+    pub fn current(&self) -> &CountdownState {
+        &self.timers[self.selected]
+    }
+
+    /// The selected timer, kept in the countdown panel from now on so that
+    /// later keys and commands act on the same one
+    pub fn pinned_mut(&mut self) -> &mut CountdownState {
+        self.pinned = true;
+        &mut self.timers[self.selected]
+    }
+
+    /// Index of the timer with a name, ignoring case
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.timers
+            .iter()
+            .position(|timer| timer.name.as_deref().is_some_and(|own| own.eq_ignore_ascii_case(name)))
+    }
+
+    /// Select the timer with a name, or keep the selection if unnamed, and pin it
+    pub fn pin(&mut self, name: Option<&str>) -> Option<&mut CountdownState> {
+        if let Some(name) = name {
+            self.selected = self.find(name)?;
+        }
+        Some(self.pinned_mut())
+    }
+}
+
+impl Default for TimersState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// RFC 3339, a date and time, a date, or a time of day (the next one to come),
+/// the last three in the given time zone or the local one
+fn parse_target(text: &str, timezone: Option<&str>, now: DateTime<Utc>) -> io::Result<DateTime<Utc>> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, format!("countdown: {message}"));
+
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let tz: Option<Tz> = match timezone {
+        Some(name) => Some(name.parse().map_err(|_| invalid(format!("unknown time zone '{name}'")))?),
+        None => None,
     };
+    let resolved = match tz {
+        Some(tz) => resolve_local(text, &tz, now),
+        None => resolve_local(text, &Local, now),
+    };
+    resolved.ok_or_else(|| invalid(format!("invalid target '{text}' (expected e.g. 2026-12-31 23:59:59 or 23:00)")))
+}
 
-    let block = Block::default()
+fn resolve_local<T: TimeZone>(text: &str, tz: &T, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let at = |naive: NaiveDateTime| match tz.from_local_datetime(&naive).earliest() {
+        Some(time) => Some(time.with_timezone(&Utc)),
+        // Skipped by a clock change: keep the offset from before it, landing just after
+        None => {
+            let before = tz.from_local_datetime(&(naive - Duration::hours(3))).earliest()?;
+            let offset = Duration::seconds(before.offset().fix().local_minus_utc() as i64);
+            Some(Utc.from_utc_datetime(&(naive - offset)))
+        }
+    };
+
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(text, format) {
+            return at(naive);
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return at(date.and_time(NaiveTime::MIN));
+    }
+
+    let time = NaiveTime::parse_from_str(text, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+        .ok()?;
+    let today = now.with_timezone(tz).date_naive();
+    let target = at(today.and_time(time))?;
+    if target > now {
+        Some(target)
+    } else {
+        at(today.succ_opt()?.and_time(time))
+    }
+}
+
+pub fn render_countdown(frame: &mut Frame, timers: &TimersState, fonts: &FontSet, area: Rect) {
+    let state = timers.current();
+    let color = state.state_color();
+
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(BORDER_DIM))
-        .title(" COUNTDOWN ")
+        .title(format!(" {} ", state.title()))
         .title_style(Style::default().fg(color).bold());
+    if timers.timers.len() > 1 {
+        let position = format!(" {}/{} ", timers.selected + 1, timers.timers.len());
+        block = block.title(Line::styled(position, Style::default().fg(TEXT_DIM)).right_aligned());
+    }
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -95,9 +494,11 @@ pub fn render_countdown(frame: &mut Frame, state: &CountdownState, area: Rect) {
 
     let time_str = state.format_time();
 
-    // Create large ASCII digits for dramatic effect
-    let large_time = if inner.height >= 3 && inner.width >= time_str.len() as u16 * 4 {
-        create_large_digits(&time_str)
+    // The biggest digits that fit, with the status line below if there is room
+    let font = fonts.fit(&time_str, inner.width, inner.height);
+    let show_status = font.is_none_or(|font| font.height < inner.height as usize);
+    let large_time = if let Some(font) = font {
+        font.render(&time_str)
     } else {
         vec![Line::from(Span::styled(
             time_str,
@@ -105,30 +506,13 @@ pub fn render_countdown(frame: &mut Frame, state: &CountdownState, area: Rect) {
         ))]
     };
 
-    let status_line = if state.remaining_seconds == 0 {
-        Line::from(Span::styled(
-            "█ EXPIRED █",
-            Style::default().fg(NEON_RED).bold(),
-        ))
-    } else if state.is_critical() {
-        Line::from(Span::styled(
-            "⚠ CRITICAL",
-            Style::default().fg(NEON_RED),
-        ))
-    } else if state.is_warning() {
-        Line::from(Span::styled(
-            "◆ WARNING",
-            Style::default().fg(NEON_ORANGE),
-        ))
-    } else {
-        Line::from(Span::styled(
-            "◇ ACTIVE",
-            Style::default().fg(NEON_GREEN),
-        ))
-    };
+    let (status, status_color) = state.status();
+    let status_line = Line::from(Span::styled(status, Style::default().fg(status_color).bold()));
 
     let mut lines = large_time;
-    lines.push(status_line);
+    if show_status {
+        lines.push(status_line);
+    }
 
     let countdown = Paragraph::new(lines)
         .alignment(Alignment::Center)
@@ -137,41 +521,108 @@ pub fn render_countdown(frame: &mut Frame, state: &CountdownState, area: Rect) {
     frame.render_widget(countdown, inner);
 }
 
-/// Create large ASCII art digits (simplified version)
-fn create_large_digits(time: &str) -> Vec<Line<'static>> {
-    // Simple block digits - each digit is 3 chars wide
-    let digit_top: [&str; 11] = [
-        "█▀█", "▀█ ", "▀▀█", "▀▀█", "█ █", "█▀▀", "█▀▀", "▀▀█", "█▀█", "█▀█", " ▄ ",
-    ];
-    let digit_mid: [&str; 11] = [
-        "█ █", " █ ", "█▀▀", " ▀█", "▀▀█", "▀▀█", "█▀█", "  █", "█▀█", "▀▀█", "   ",
-    ];
-    let digit_bot: [&str; 11] = [
-        "▀▀▀", "▀▀▀", "▀▀▀", "▀▀▀", "  ▀", "▀▀▀", "▀▀▀", "  ▀", "▀▀▀", "▀▀▀", " ▀ ",
-    ];
-
-    let mut top = String::new();
-    let mut mid = String::new();
-    let mut bot = String::new();
-
-    for ch in time.chars() {
-        let idx = match ch {
-            '0'..='9' => ch as usize - '0' as usize,
-            ':' => 10,
-            _ => continue,
+/// Every timer stacked: name, time and status, with a bar of the time left
+/// when there is room
+pub fn render_timers(frame: &mut Frame, timers: &TimersState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_DIM))
+        .title(" TIMERS ")
+        .title_style(Style::default().fg(NEON_MAGENTA).bold());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows_per_timer = if inner.height as usize >= timers.timers.len() * 2 { 2 } else { 1 };
+    let name_width = timers.timers.iter().map(|t| t.title().chars().count()).max().unwrap_or(0);
+    let mut rows = inner.rows();
+
+    for (index, timer) in timers.timers.iter().enumerate() {
+        let Some(row) = rows.next() else {
+            break;
         };
+        let color = timer.state_color();
+        let (status, status_color) = timer.status();
+        let marker = if index == timers.selected { "▸ " } else { "  " };
+
+        let line = Line::from(vec![
+            Span::styled(marker, Style::default().fg(NEON_MAGENTA)),
+            Span::styled(format!("{:<name_width$}  ", timer.title()), Style::default().fg(timer.color).bold()),
+            Span::styled(format!("{:>12}  ", timer.format_time()), Style::default().fg(color).bold()),
+            Span::styled(status, Style::default().fg(status_color)),
+        ]);
+        frame.render_widget(line, row);
+
+        if rows_per_timer == 2 {
+            let Some(row) = rows.next() else {
+                break;
+            };
+            let width = row.width.saturating_sub(2) as usize;
+            let filled = timer.fraction_left().map_or(0, |left| (left * width as f64).round() as usize).min(width);
+            let bar = Line::from(vec![
+                Span::raw("  "),
+                Span::styled("━".repeat(filled), Style::default().fg(color)),
+                Span::styled("─".repeat(width - filled), Style::default().fg(BORDER_DIM)),
+            ]);
+            frame.render_widget(bar, row);
+        }
+    }
+}
 
-        top.push_str(digit_top[idx]);
-        top.push(' ');
-        mid.push_str(digit_mid[idx]);
-        mid.push(' ');
-        bot.push_str(digit_bot[idx]);
-        bot.push(' ');
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
     }
 
-    vec![
-        Line::from(top),
-        Line::from(mid),
-        Line::from(bot),
-    ]
+    fn target(text: &str, timezone: &str, now: &str) -> DateTime<Utc> {
+        parse_target(text, Some(timezone), utc(now)).unwrap()
+    }
+
+    #[test]
+    fn parses_absolute_targets() {
+        let now = "2026-06-01T12:00:00Z";
+        assert_eq!(target("2026-12-31T23:59:59+01:00", "UTC", now), utc("2026-12-31T22:59:59Z"));
+        assert_eq!(target("2026-12-31 23:59:59", "Europe/Berlin", now), utc("2026-12-31T22:59:59Z"));
+        assert_eq!(target("2026-07-01 08:30", "America/New_York", now), utc("2026-07-01T12:30:00Z"));
+        assert_eq!(target("2026-07-01T08:30:00", "Asia/Tokyo", now), utc("2026-06-30T23:30:00Z"));
+        assert_eq!(target("2026-07-01", "Europe/Berlin", now), utc("2026-06-30T22:00:00Z"));
+    }
+
+    #[test]
+    fn time_of_day_is_the_next_one_in_the_zone() {
+        // 14:00 in Berlin, so 23:00 is still ahead today and 09:15:30 is tomorrow
+        let now = "2026-06-01T12:00:00Z";
+        assert_eq!(target("23:00", "Europe/Berlin", now), utc("2026-06-01T21:00:00Z"));
+        assert_eq!(target("09:15:30", "Europe/Berlin", now), utc("2026-06-02T07:15:30Z"));
+        // Already past midnight in Tokyo
+        assert_eq!(target("08:00", "Asia/Tokyo", "2026-06-01T16:00:00Z"), utc("2026-06-01T23:00:00Z"));
+    }
+
+    #[test]
+    fn times_skipped_by_a_clock_change_land_just_after_it() {
+        // Berlin clocks jump from 02:00 to 03:00 on 2026-03-29
+        let now = "2026-03-01T00:00:00Z";
+        assert_eq!(target("2026-03-29 02:30", "Europe/Berlin", now), utc("2026-03-29T01:30:00Z"));
+        assert_eq!(target("02:30", "Europe/Berlin", "2026-03-28T12:00:00Z"), utc("2026-03-29T01:30:00Z"));
+        // Repeated times pick the first of the two
+        assert_eq!(target("2026-10-25 02:30", "Europe/Berlin", now), utc("2026-10-25T00:30:00Z"));
+    }
+
+    #[test]
+    fn rejects_bad_targets() {
+        let now = utc("2026-06-01T12:00:00Z");
+        for text in ["tomorrow", "25:00", "2026-13-01", "2026-02-30 10:00", ""] {
+            let error = parse_target(text, Some("UTC"), now).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!("countdown: invalid target '{text}' (expected e.g. 2026-12-31 23:59:59 or 23:00)")
+            );
+        }
+        let error = parse_target("23:00", Some("Mars/Olympus"), now).unwrap_err();
+        assert_eq!(error.to_string(), "countdown: unknown time zone 'Mars/Olympus'");
+    }
 }
@@ -1,6 +1,6 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, BorderType, Gauge, Sparkline},
+    widgets::{Axis, Block, Borders, BorderType, Chart, Dataset, Gauge, GraphType, Sparkline},
 };
 use std::collections::VecDeque;
 
@@ -8,34 +8,68 @@ use crate::data::system_stats::SystemStats;
 use crate::ui::theme::*;
 
 const HISTORY_SIZE: usize = 60;
+/// A core at or above this usage is flagged as pegged
+const PEGGED_PERCENT: f64 = 95.0;
+/// Width of one per-core bar, e.g. "C03 ██████░░░░  97%"
+const CORE_CELL_WIDTH: u16 = 19;
+/// Minimum inner size for the chart with axes
+const CHART_MIN_HEIGHT: u16 = 12;
+const CHART_MIN_WIDTH: u16 = 40;
 
 pub struct CpuGaugeState {
     pub current: f64,
     pub history: VecDeque<u64>,
+    /// Usage of each core in percent
+    pub cores: Vec<f64>,
+    /// History of the busiest core, to spot a single pegged core
+    pub peak_history: VecDeque<u64>,
+    /// Average core frequency in MHz
+    pub frequency: u64,
+    /// 1, 5 and 15 minute load averages
+    pub load_average: [f64; 3],
+    /// Seconds between history samples
+    pub interval: f64,
 }
 
 impl CpuGaugeState {
     pub fn new() -> Self {
-        let mut history = VecDeque::with_capacity(HISTORY_SIZE);
         // Initialize with zeros
-        for _ in 0..HISTORY_SIZE {
-            history.push_back(0);
-        }
+        let history: VecDeque<u64> = std::iter::repeat_n(0, HISTORY_SIZE).collect();
 
         Self {
             current: 0.0,
+            peak_history: history.clone(),
             history,
+            cores: Vec::new(),
+            frequency: 0,
+            load_average: [0.0; 3],
+            interval: 1.0,
         }
     }
 
     pub fn update(&mut self, stats: &SystemStats) {
         self.current = stats.cpu_usage;
+        self.cores = stats.core_usage.clone();
+        self.frequency = stats.cpu_frequency;
+        self.load_average = stats.load_average;
+        self.interval = stats.interval;
 
         // Add to history
         if self.history.len() >= HISTORY_SIZE {
             self.history.pop_front();
+            self.peak_history.pop_front();
         }
         self.history.push_back(self.current as u64);
+        self.peak_history.push_back(self.busiest_core().map_or(0.0, |(_, usage)| usage) as u64);
+    }
+
+    /// Index and usage of the busiest core
+    pub fn busiest_core(&self) -> Option<(usize, f64)> {
+        self.cores
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
     }
 }
 
@@ -46,12 +80,17 @@ impl Default for CpuGaugeState {
 }
 
 pub fn render_cpu_gauge(frame: &mut Frame, state: &CpuGaugeState, area: Rect) {
+    let [one, five, fifteen] = state.load_average;
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(BORDER_DIM))
         .title(" CPU ")
-        .title_style(Style::default().fg(NEON_CYAN).bold());
+        .title_style(Style::default().fg(NEON_CYAN).bold())
+        .title(
+            Line::styled(format!(" LOAD {one:.2} {five:.2} {fifteen:.2} "), Style::default().fg(TEXT_DIM))
+                .right_aligned(),
+        );
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -60,26 +99,140 @@ pub fn render_cpu_gauge(frame: &mut Frame, state: &CpuGaugeState, area: Rect) {
         return;
     }
 
-    // Split inner area for gauge and sparkline
+    // Rows for the per-core bars if there is room for them
+    let columns = (inner.width / CORE_CELL_WIDTH).max(1) as usize;
+    let core_rows = state.cores.len().div_ceil(columns) as u16;
+    let show_cores = core_rows > 0 && inner.height >= core_rows + 4;
+    let show_chart = inner.height >= CHART_MIN_HEIGHT + core_rows && inner.width >= CHART_MIN_WIDTH;
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(if show_cores { 1 } else { 0 }),
+            Constraint::Length(if show_cores { core_rows } else { 0 }),
+            Constraint::Min(1),
+        ])
         .split(inner);
 
     // Render gauge
     let color = gauge_color(state.current);
+    let mut label = format!("{:.1}%", state.current);
+    if let Some((core, usage)) = state.busiest_core().filter(|&(_, usage)| usage >= PEGGED_PERCENT) {
+        label.push_str(&format!(" · C{core:02} {usage:.0}%"));
+    }
     let gauge = Gauge::default()
-        .ratio(state.current / 100.0)
+        .ratio((state.current / 100.0).clamp(0.0, 1.0))
         .gauge_style(Style::default().fg(color).bg(DARK_BG))
-        .label(format!("{:.1}%", state.current));
+        .label(label);
 
     frame.render_widget(gauge, chunks[0]);
 
-    // Render sparkline
-    let data: Vec<u64> = state.history.iter().copied().collect();
-    let sparkline = Sparkline::default()
-        .data(&data)
-        .style(Style::default().fg(color));
+    if show_cores {
+        let frequency = if state.frequency > 0 {
+            format!("{:.2} GHz", state.frequency as f64 / 1000.0)
+        } else {
+            "--- GHz".to_string()
+        };
+        let info = Line::from(vec![
+            Span::styled(format!("{} CORES", state.cores.len()), Style::default().fg(TEXT_DIM)),
+            Span::styled(" · ", Style::default().fg(BORDER_DIM)),
+            Span::styled(frequency, Style::default().fg(NEON_CYAN)),
+        ]);
+        frame.render_widget(info, chunks[1]);
+        render_cores(frame, state, columns, chunks[2]);
+    }
+
+    if show_chart {
+        render_chart(frame, state, chunks[3]);
+    } else {
+        // Render sparkline
+        let data: Vec<u64> = state.history.iter().copied().collect();
+        let sparkline = Sparkline::default()
+            .data(&data)
+            .style(Style::default().fg(color));
+
+        frame.render_widget(sparkline, chunks[3]);
+    }
+}
+
+/// One bar per core, laid out in columns
+fn render_cores(frame: &mut Frame, state: &CpuGaugeState, columns: usize, area: Rect) {
+    let bar_width = CORE_CELL_WIDTH as usize - 10;
+    let rows = state.cores.len().div_ceil(columns);
+
+    let lines: Vec<Line> = (0..rows)
+        .map(|row| {
+            let spans = (0..columns)
+                .filter_map(|column| {
+                    let index = column * rows + row;
+                    let usage = *state.cores.get(index)?;
+                    let filled = ((usage / 100.0) * bar_width as f64).round() as usize;
+                    let color = gauge_color(usage);
+                    let pegged = usage >= PEGGED_PERCENT;
+
+                    Some(vec![
+                        Span::styled(
+                            format!("C{index:02} "),
+                            Style::default().fg(if pegged { NEON_RED } else { TEXT_DIM }),
+                        ),
+                        Span::styled("█".repeat(filled.min(bar_width)), Style::default().fg(color)),
+                        Span::styled("░".repeat(bar_width - filled.min(bar_width)), Style::default().fg(BORDER_DIM)),
+                        Span::styled(format!("{usage:>4.0}% "), Style::default().fg(color)),
+                    ])
+                })
+                .flatten()
+                .collect::<Vec<_>>();
+            Line::from(spans)
+        })
+        .collect();
+
+    frame.render_widget(Text::from(lines), area);
+}
 
-    frame.render_widget(sparkline, chunks[1]);
+/// History chart with time and percentage axes for large panels
+fn render_chart(frame: &mut Frame, state: &CpuGaugeState, area: Rect) {
+    let points = |history: &VecDeque<u64>| -> Vec<(f64, f64)> {
+        let start = history.len() as f64;
+        history
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| (i as f64 - start + 1.0, value as f64))
+            .collect()
+    };
+    let total = points(&state.history);
+    let peak = points(&state.peak_history);
+
+    let datasets = vec![
+        Dataset::default()
+            .name("busiest core")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(NEON_MAGENTA))
+            .data(&peak),
+        Dataset::default()
+            .name("total")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(gauge_color(state.current)))
+            .data(&total),
+    ];
+
+    let span = HISTORY_SIZE as f64 * state.interval;
+    let axis_style = Style::default().fg(TEXT_DIM);
+    let chart = Chart::new(datasets)
+        .x_axis(
+            Axis::default()
+                .style(axis_style)
+                .bounds([-(HISTORY_SIZE as f64) + 1.0, 0.0])
+                .labels([format!("-{span:.0}s"), format!("-{:.0}s", span / 2.0), "now".to_string()]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(axis_style)
+                .bounds([0.0, 100.0])
+                .labels(["0%", "50%", "100%"]),
+        );
+
+    frame.render_widget(chart, area);
 }
@@ -0,0 +1,210 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, BorderType, Sparkline},
+};
+use std::collections::VecDeque;
+
+use crate::config::{DiskConfig, RateUnit, UnitPrefix};
+use crate::data::system_stats::{format_bytes, format_bytes_per_sec, FilesystemReading, SystemStats};
+use crate::ui::theme::*;
+
+const HISTORY_SIZE: usize = 30;
+/// A fullness alert re-arms once the filesystem is this much below the limit
+const HYSTERESIS: f64 = 2.0;
+
+/// Read and write history of one block device
+pub struct DeviceTraffic {
+    pub device: String,
+    pub read_rate: u64,
+    pub write_rate: u64,
+    pub read_history: VecDeque<u64>,
+    pub write_history: VecDeque<u64>,
+}
+
+impl DeviceTraffic {
+    fn new(device: &str) -> Self {
+        let history: VecDeque<u64> = std::iter::repeat_n(0, HISTORY_SIZE).collect();
+
+        Self {
+            device: device.to_string(),
+            read_rate: 0,
+            write_rate: 0,
+            read_history: history.clone(),
+            write_history: history,
+        }
+    }
+
+    fn push(&mut self, read: u64, written: u64) {
+        self.read_rate = read;
+        self.write_rate = written;
+
+        if self.read_history.len() >= HISTORY_SIZE {
+            self.read_history.pop_front();
+            self.write_history.pop_front();
+        }
+        self.read_history.push_back(read);
+        self.write_history.push_back(written);
+    }
+}
+
+pub struct DiskMonitorState {
+    pub filesystems: Vec<FilesystemReading>,
+    pub devices: Vec<DeviceTraffic>,
+    mounts: Vec<String>,
+    alert_percent: f64,
+    /// Mount points above the alert limit that were already reported
+    alarmed: Vec<String>,
+    /// Alerts raised since the last `take_alerts`
+    alerts: Vec<String>,
+}
+
+impl DiskMonitorState {
+    pub fn new() -> Self {
+        Self::with_config(&DiskConfig::default())
+    }
+
+    pub fn with_config(config: &DiskConfig) -> Self {
+        Self {
+            filesystems: Vec::new(),
+            devices: Vec::new(),
+            mounts: config.mounts.clone(),
+            alert_percent: config.alert_percent,
+            alarmed: Vec::new(),
+            alerts: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, stats: &SystemStats) {
+        self.filesystems = stats
+            .filesystems
+            .iter()
+            .filter(|fs| self.mounts.is_empty() || self.mounts.contains(&fs.mount_point))
+            .cloned()
+            .collect();
+
+        let mut previous = std::mem::take(&mut self.devices);
+        for reading in &stats.disk_io {
+            let mut traffic = match previous.iter().position(|d| d.device == reading.device) {
+                Some(index) => previous.swap_remove(index),
+                None => DeviceTraffic::new(&reading.device),
+            };
+            traffic.push(reading.read, reading.written);
+            self.devices.push(traffic);
+        }
+
+        if self.alert_percent > 0.0 {
+            self.check_fullness();
+        }
+    }
+
+    fn check_fullness(&mut self) {
+        for fs in &self.filesystems {
+            let used = fs.used_percentage();
+            let alarmed = self.alarmed.contains(&fs.mount_point);
+
+            if !alarmed && used >= self.alert_percent {
+                self.alarmed.push(fs.mount_point.clone());
+                self.alerts.push(format!(
+                    "STORAGE CRITICAL: {} at {used:.0}% full ({} free)",
+                    fs.mount_point,
+                    format_bytes(fs.available)
+                ));
+            } else if alarmed && used < self.alert_percent - HYSTERESIS {
+                self.alarmed.retain(|mount| mount != &fs.mount_point);
+            }
+        }
+    }
+
+    /// Fullness alerts raised since the last call
+    pub fn take_alerts(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.alerts)
+    }
+}
+
+impl Default for DiskMonitorState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn render_disk_monitor(frame: &mut Frame, state: &DiskMonitorState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_DIM))
+        .title(" STORAGE ")
+        .title_style(Style::default().fg(NEON_ORANGE).bold());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner.height < 1 {
+        return;
+    }
+
+    // Filesystems first, then two rows per device for whatever is left
+    let fs_rows = (state.filesystems.len() as u16).min(inner.height);
+    let device_rows = (inner.height - fs_rows) / 2;
+    let [fs_area, device_area] =
+        Layout::vertical([Constraint::Length(fs_rows), Constraint::Min(0)]).areas(inner);
+
+    let label_width = state
+        .filesystems
+        .iter()
+        .map(|fs| fs.mount_point.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(inner.width as usize / 3);
+
+    for (fs, row) in state.filesystems.iter().zip(fs_area.rows()) {
+        frame.render_widget(filesystem_line(fs, label_width, row.width as usize), row);
+    }
+
+    let device_areas = Layout::vertical(vec![Constraint::Length(2); device_rows as usize]).split(device_area);
+    for (device, &area) in state.devices.iter().zip(device_areas.iter()) {
+        render_device(frame, device, area);
+    }
+}
+
+/// Mount point, usage bar, percentage and free space on one line
+fn filesystem_line(fs: &FilesystemReading, label_width: usize, width: usize) -> Line<'static> {
+    let used = fs.used_percentage();
+    let color = gauge_color(used);
+    let label: String = fs.mount_point.chars().take(label_width).collect();
+    let free = format!(" {:>3.0}% {} free", used, format_bytes(fs.available));
+    let bar_width = width.saturating_sub(label_width + 1 + free.chars().count());
+    let filled = (((used / 100.0) * bar_width as f64).round() as usize).min(bar_width);
+
+    Line::from(vec![
+        Span::styled(format!("{label:<label_width$} "), Style::default().fg(TEXT_PRIMARY)),
+        Span::styled("█".repeat(filled), Style::default().fg(color)),
+        Span::styled("░".repeat(bar_width - filled), Style::default().fg(BORDER_DIM)),
+        Span::styled(free, Style::default().fg(color)),
+    ])
+}
+
+/// Device name with its rates, and read and write sparklines side by side
+fn render_device(frame: &mut Frame, device: &DeviceTraffic, area: Rect) {
+    let [label_area, spark_area] = Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(area);
+
+    let rate = |bytes| format_bytes_per_sec(bytes, RateUnit::Bytes, UnitPrefix::Iec);
+    let label = Line::from(vec![
+        Span::styled(format!("{} ", device.device), Style::default().fg(TEXT_PRIMARY)),
+        Span::styled("R ", Style::default().fg(NEON_CYAN)),
+        Span::styled(rate(device.read_rate), Style::default().fg(TEXT_PRIMARY)),
+        Span::styled("  W ", Style::default().fg(NEON_ORANGE)),
+        Span::styled(rate(device.write_rate), Style::default().fg(TEXT_PRIMARY)),
+    ]);
+    frame.render_widget(label, label_area);
+
+    let [read_area, write_area] =
+        Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).spacing(1).areas(spark_area);
+    for (history, color, area) in [
+        (&device.read_history, NEON_CYAN, read_area),
+        (&device.write_history, NEON_ORANGE, write_area),
+    ] {
+        let data: Vec<u64> = history.iter().copied().collect();
+        let visible = &data[data.len().saturating_sub(area.width as usize)..];
+        frame.render_widget(Sparkline::default().data(visible).style(Style::default().fg(color)), area);
+    }
+}
@@ -11,7 +11,7 @@ use crate::ui::theme::*;
 
 const MAX_LOGS: usize = 100;
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LogLevel {
     Info,
     Warn,
@@ -54,6 +54,8 @@ pub struct LogEntry {
 
 pub struct FakeLogsState {
     pub logs: VecDeque<LogEntry>,
+    /// City/facility names used in messages, shared with the world map
+    locations: Vec<String>,
     tick_counter: u64,
     next_log_at: u64,
 }
@@ -62,6 +64,7 @@ impl FakeLogsState {
     pub fn new() -> Self {
         let mut state = Self {
             logs: VecDeque::with_capacity(MAX_LOGS),
+            locations: Vec::new(),
             tick_counter: 0,
             next_log_at: 0,
         };
@@ -74,6 +77,11 @@ impl FakeLogsState {
         state
     }
 
+    /// Set the location names used in generated messages
+    pub fn set_locations(&mut self, locations: Vec<String>) {
+        self.locations = locations;
+    }
+
     pub fn tick(&mut self) {
         self.tick_counter += 1;
 
@@ -97,6 +105,17 @@ impl FakeLogsState {
             return;
         }
 
+        // Occasionally mention one of the map's locations
+        if !self.locations.is_empty() && rng.gen_bool(0.08) {
+            let location = &self.locations[rng.gen_range(0..self.locations.len())];
+            self.add_log(LogEntry {
+                timestamp: Local::now().format("%H:%M:%S%.3f").to_string(),
+                level: LogLevel::Info,
+                message: format!("Relay node {} routing via {}", location, random_ip()),
+            });
+            return;
+        }
+
         let entry = match rng.gen_range(0..10) {
             0 => LogEntry {
                 timestamp: Local::now().format("%H:%M:%S%.3f").to_string(),
@@ -153,6 +172,15 @@ impl FakeLogsState {
         self.add_log(entry);
     }
 
+    /// Push a log line from outside the random generator
+    pub fn push(&mut self, level: LogLevel, message: impl Into<String>) {
+        self.add_log(LogEntry {
+            timestamp: Local::now().format("%H:%M:%S%.3f").to_string(),
+            level,
+            message: message.into(),
+        });
+    }
+
     fn add_log(&mut self, entry: LogEntry) {
         if self.logs.len() >= MAX_LOGS {
             self.logs.pop_front();
@@ -0,0 +1,221 @@
+use ratatui::{
+    prelude::*,
+    widgets::{
+        canvas::{Canvas, Circle, Line, Points},
+        Block, Borders, BorderType,
+    },
+};
+
+use crate::config::GlobeConfig;
+use crate::data::coastline::coastline;
+use crate::ui::theme::*;
+use crate::widgets::world_map::{
+    arc_points, great_circle_point, network_title, node_color, place_labels, MapNode, WorldMapState,
+};
+use crate::TICKS_PER_SECOND;
+
+/// Canvas margin around the unit sphere
+const GLOBE_MARGIN: f64 = 1.08;
+/// Spacing of the latitude and longitude grid lines, in degrees
+const GRATICULE_STEP: i32 = 30;
+
+/// Rotating wireframe globe; the network itself comes from `WorldMapState`
+pub struct GlobeState {
+    /// Longitude facing the viewer, in degrees
+    pub rotation: f64,
+    /// Latitude facing the viewer, in degrees
+    pub tilt: f64,
+    /// Degrees of rotation per tick
+    speed: f64,
+    coastline: Vec<(f64, f64)>,
+    graticule: Vec<(f64, f64)>,
+}
+
+impl GlobeState {
+    pub fn new() -> Self {
+        Self::with_config(&GlobeConfig::default())
+    }
+
+    pub fn with_config(config: &GlobeConfig) -> Self {
+        // Grid lines sampled as (lon, lat) points every 3 degrees
+        let mut graticule = Vec::new();
+        for lat in (-90 + GRATICULE_STEP..90).step_by(GRATICULE_STEP as usize) {
+            graticule.extend((-180..180).step_by(3).map(|lon| (lon as f64, lat as f64)));
+        }
+        for lon in (-180..180).step_by(GRATICULE_STEP as usize) {
+            graticule.extend((-90..=90).step_by(3).map(|lat| (lon as f64, lat as f64)));
+        }
+
+        Self {
+            rotation: 0.0,
+            tilt: config.tilt.clamp(-90.0, 90.0),
+            speed: config.speed / TICKS_PER_SECOND,
+            coastline: coastline(),
+            graticule,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.rotate(self.speed);
+    }
+
+    /// Turn the globe by some degrees of longitude (positive = eastwards)
+    pub fn rotate(&mut self, degrees: f64) {
+        self.rotation = (self.rotation + degrees + 180.0).rem_euclid(360.0) - 180.0;
+    }
+
+    /// Tilt the globe by some degrees of latitude (positive = north pole towards the viewer)
+    pub fn tilt_by(&mut self, degrees: f64) {
+        self.tilt = (self.tilt + degrees).clamp(-90.0, 90.0);
+    }
+
+    fn projection(&self) -> Orthographic {
+        let tilt = self.tilt.to_radians();
+        Orthographic {
+            sin_tilt: tilt.sin(),
+            cos_tilt: tilt.cos(),
+            rotation: self.rotation,
+        }
+    }
+}
+
+impl Default for GlobeState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Orthographic projection of the sphere onto the unit disc
+struct Orthographic {
+    sin_tilt: f64,
+    cos_tilt: f64,
+    rotation: f64,
+}
+
+impl Orthographic {
+    /// Canvas position of a point, or `None` on the far hemisphere
+    fn project(&self, lat: f64, lon: f64) -> Option<(f64, f64)> {
+        let lat = lat.to_radians();
+        let dlon = (lon - self.rotation).to_radians();
+        let (sin_lat, cos_lat) = lat.sin_cos();
+
+        let depth = self.sin_tilt * sin_lat + self.cos_tilt * cos_lat * dlon.cos();
+        if depth < 0.0 {
+            return None;
+        }
+
+        Some((
+            cos_lat * dlon.sin(),
+            self.cos_tilt * sin_lat - self.sin_tilt * cos_lat * dlon.cos(),
+        ))
+    }
+
+    fn project_node(&self, node: &MapNode) -> Option<(f64, f64)> {
+        self.project(node.lat, node.lon)
+    }
+}
+
+pub fn render_globe(frame: &mut Frame, globe: &GlobeState, map: &WorldMapState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_DIM))
+        .title(network_title(map))
+        .title_style(Style::default().fg(MAP_NODE_ACTIVE).bold());
+
+    // Terminal cells are about twice as tall as wide; size the bounds so
+    // the sphere comes out round
+    let inner = block.inner(area);
+    let (width, height) = (inner.width.max(1) as f64, inner.height.max(1) as f64 * 2.0);
+    let (x_half, y_half) = if width >= height {
+        (GLOBE_MARGIN * width / height, GLOBE_MARGIN)
+    } else {
+        (GLOBE_MARGIN, GLOBE_MARGIN * height / width)
+    };
+    let bounds = ([-x_half, x_half], [-y_half, y_half]);
+
+    let projection = globe.projection();
+    let project_all = |points: &[(f64, f64)]| -> Vec<(f64, f64)> {
+        points
+            .iter()
+            .filter_map(|&(lon, lat)| projection.project(lat, lon))
+            .collect()
+    };
+    let graticule = project_all(&globe.graticule);
+    let coastline = project_all(&globe.coastline);
+    let labels = place_labels(map, inner, bounds, 0.04, |node| projection.project_node(node));
+
+    let canvas = Canvas::default()
+        .block(block)
+        .x_bounds(bounds.0)
+        .y_bounds(bounds.1)
+        .marker(symbols::Marker::Braille)
+        .paint(|ctx| {
+            // Wireframe: grid, limb and coastlines
+            ctx.draw(&Points { coords: &graticule, color: BORDER_DIM });
+            ctx.draw(&Circle { x: 0.0, y: 0.0, radius: 1.0, color: MAP_OUTLINE });
+            ctx.draw(&Points { coords: &coastline, color: MAP_OUTLINE });
+            ctx.layer();
+
+            // Connection arcs on the visible hemisphere
+            for conn in &map.connections {
+                if !conn.active && conn.progress == 0.0 {
+                    continue;
+                }
+
+                let from = &map.nodes[conn.from];
+                let to = &map.nodes[conn.to];
+                let color = if conn.active { MAP_CONNECTION } else { BORDER_DIM };
+
+                let points: Vec<Option<(f64, f64)>> = arc_points(from, to, conn.progress as f64)
+                    .into_iter()
+                    .map(|(lat, lon)| projection.project(lat, lon))
+                    .collect();
+                for pair in points.windows(2) {
+                    if let [Some((x1, y1)), Some((x2, y2))] = *pair {
+                        ctx.draw(&Line { x1, y1, x2, y2, color });
+                    }
+                }
+
+                for &packet in &conn.packets {
+                    let (lat, lon) = great_circle_point(from, to, packet as f64);
+                    if let Some((x, y)) = projection.project(lat, lon) {
+                        ctx.draw(&Circle { x, y, radius: 0.01, color: TEXT_HIGHLIGHT });
+                    }
+                }
+            }
+
+            // Nodes with their arrival pulses
+            for node in &map.nodes {
+                let Some((x, y)) = projection.project_node(node) else {
+                    continue;
+                };
+
+                if node.pulse > 0.0 {
+                    ctx.draw(&Circle {
+                        x,
+                        y,
+                        radius: 0.03 + (1.0 - node.pulse as f64) * 0.08,
+                        color: if node.pulse > 0.5 { TEXT_HIGHLIGHT } else { MAP_CONNECTION },
+                    });
+                }
+
+                ctx.draw(&Circle {
+                    x,
+                    y,
+                    radius: if node.active { 0.03 } else { 0.02 },
+                    color: node_color(node),
+                });
+            }
+
+            for label in &labels {
+                ctx.print(
+                    label.x,
+                    label.y,
+                    Span::styled(label.text.clone(), Style::default().fg(label.color)),
+                );
+            }
+        });
+
+    frame.render_widget(canvas, area);
+}
@@ -43,13 +43,13 @@ impl HexDumpState {
         self.tick_counter += 1;
 
         // Add new line every 10 ticks
-        if self.tick_counter % 10 == 0 {
+        if self.tick_counter.is_multiple_of(10) {
             self.add_line();
             self.scroll_offset = self.lines.len().saturating_sub(10);
         }
 
         // Update highlight positions
-        if self.tick_counter % 5 == 0 {
+        if self.tick_counter.is_multiple_of(5) {
             let mut rng = rand::thread_rng();
             for line in &mut self.lines {
                 if rng.gen_bool(0.1) {
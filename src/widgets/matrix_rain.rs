@@ -4,8 +4,15 @@ use ratatui::{
 };
 use rand::Rng;
 
+use crate::config::MatrixConfig;
 use crate::data::fake_data::matrix_chars;
 use crate::ui::theme::*;
+use crate::TICKS_PER_SECOND;
+
+/// Ticks a seeker drop falls before locking in its character
+const SEEK_TICKS: u64 = 24;
+/// Ticks a released character takes to fall out of view
+const FALL_TICKS: u64 = 18;
 
 /// A single falling drop in the matrix rain
 #[derive(Clone)]
@@ -57,22 +64,176 @@ impl Drop {
     }
 }
 
+/// Durations of the hidden message reveal phases, in ticks
+#[derive(Clone, Copy)]
+struct RevealTiming {
+    lock: u64,
+    hold: u64,
+    dissolve: u64,
+}
+
+impl RevealTiming {
+    fn from_config(config: &MatrixConfig) -> Self {
+        let ticks = |secs: f32| (secs.max(0.0) * TICKS_PER_SECOND as f32) as u64;
+        Self {
+            lock: ticks(config.reveal_secs),
+            hold: ticks(config.hold_secs),
+            dissolve: ticks(config.dissolve_secs),
+        }
+    }
+}
+
+/// How a single character of the hidden message currently looks
+enum GlyphState {
+    /// Not yet locked; a seeker drop is `distance` ticks above it
+    Seeking { distance: u64 },
+    /// Locked in place, `age` ticks after locking
+    Locked { age: u64 },
+    /// Released and falling away, `age` ticks after release
+    Falling { age: u64 },
+    Hidden,
+}
+
+/// A hidden message being locked into the rain and dissolved again
+struct Reveal {
+    /// Words of the message, without whitespace
+    words: Vec<Vec<char>>,
+    /// Tick (from reveal start) at which each glyph locks in
+    lock_at: Vec<u64>,
+    /// Tick (from dissolve start) at which each glyph is released
+    release_at: Vec<u64>,
+    elapsed: u64,
+    timing: RevealTiming,
+}
+
+impl Reveal {
+    fn new(message: &str, timing: RevealTiming) -> Self {
+        let mut rng = rand::thread_rng();
+        let words: Vec<Vec<char>> = message
+            .split_whitespace()
+            .map(|word| word.chars().collect())
+            .collect();
+        let glyphs: usize = words.iter().map(Vec::len).sum();
+
+        Self {
+            words,
+            lock_at: (0..glyphs).map(|_| rng.gen_range(0..=timing.lock)).collect(),
+            release_at: (0..glyphs).map(|_| rng.gen_range(0..=timing.dissolve)).collect(),
+            elapsed: 0,
+            timing,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed > self.timing.lock + self.timing.hold + self.timing.dissolve + FALL_TICKS
+    }
+
+    fn glyph_state(&self, index: usize) -> GlyphState {
+        let lock_at = self.lock_at[index];
+        let release_at = self.timing.lock + self.timing.hold + self.release_at[index];
+
+        if self.elapsed < lock_at {
+            let distance = lock_at - self.elapsed;
+            if distance <= SEEK_TICKS {
+                GlyphState::Seeking { distance }
+            } else {
+                GlyphState::Hidden
+            }
+        } else if self.elapsed < release_at {
+            GlyphState::Locked { age: self.elapsed - lock_at }
+        } else if self.elapsed - release_at < FALL_TICKS {
+            GlyphState::Falling { age: self.elapsed - release_at }
+        } else {
+            GlyphState::Hidden
+        }
+    }
+
+    /// Word-wrap the message into the area and return each glyph with its
+    /// position relative to the area, centered horizontally and vertically
+    fn layout(&self, width: u16, height: u16) -> Vec<(char, u16, u16)> {
+        let width = width as usize;
+        if width == 0 {
+            return Vec::new();
+        }
+
+        // Greedily pack words into lines, breaking words longer than a line
+        let mut lines: Vec<Vec<char>> = vec![Vec::new()];
+        for word in &self.words {
+            for chunk in word.chunks(width) {
+                let line = lines.last_mut().expect("at least one line");
+                let needed = if line.is_empty() { chunk.len() } else { line.len() + 1 + chunk.len() };
+                if needed > width && !line.is_empty() {
+                    lines.push(chunk.to_vec());
+                } else {
+                    if !line.is_empty() {
+                        line.push(' ');
+                    }
+                    line.extend_from_slice(chunk);
+                }
+            }
+        }
+
+        let top = (height as usize).saturating_sub(lines.len()) / 2;
+        let mut glyphs = Vec::new();
+        for (row, line) in lines.iter().enumerate() {
+            let left = (width - line.len()) / 2;
+            for (col, &ch) in line.iter().enumerate() {
+                if ch != ' ' {
+                    glyphs.push((ch, (left + col) as u16, (top + row) as u16));
+                }
+            }
+        }
+        glyphs
+    }
+}
+
 pub struct MatrixRainState {
     drops: Vec<Drop>,
     char_set: Vec<char>,
     width: u16,
     height: u16,
+    tick_counter: u64,
+    reveal: Option<Reveal>,
+    /// Configured message used for automatic and on-demand reveals
+    message: Option<String>,
+    timing: RevealTiming,
+    /// Ticks between automatic reveals (0 = disabled)
+    reveal_interval: u64,
+    next_reveal_at: u64,
 }
 
 impl MatrixRainState {
     pub fn new() -> Self {
+        Self::with_config(&MatrixConfig::default())
+    }
+
+    pub fn with_config(config: &MatrixConfig) -> Self {
         let char_set = matrix_chars();
+        let reveal_interval = (config.interval_secs.max(0.0) * TICKS_PER_SECOND as f32) as u64;
 
         Self {
             drops: Vec::new(),
             char_set,
             width: 0,
             height: 0,
+            tick_counter: 0,
+            reveal: None,
+            message: config.message.clone(),
+            timing: RevealTiming::from_config(config),
+            reveal_interval,
+            next_reveal_at: reveal_interval,
+        }
+    }
+
+    /// Start revealing `message`, or the configured message if `None`.
+    /// Returns false if there is nothing to reveal.
+    pub fn reveal(&mut self, message: Option<&str>) -> bool {
+        match message.or(self.message.as_deref()) {
+            Some(text) if !text.trim().is_empty() => {
+                self.reveal = Some(Reveal::new(text, self.timing));
+                true
+            }
+            _ => false,
         }
     }
 
@@ -88,9 +249,26 @@ impl MatrixRainState {
     }
 
     pub fn tick(&mut self) {
+        self.tick_counter += 1;
+
         for drop in &mut self.drops {
             drop.tick(self.height, &self.char_set);
         }
+
+        if let Some(reveal) = &mut self.reveal {
+            reveal.elapsed += 1;
+            if reveal.is_finished() {
+                self.reveal = None;
+            }
+        }
+
+        // Automatic reveals of the configured message
+        if self.reveal_interval > 0 && self.tick_counter >= self.next_reveal_at {
+            if self.reveal.is_none() {
+                self.reveal(None);
+            }
+            self.next_reveal_at = self.tick_counter + self.reveal_interval;
+        }
     }
 }
 
@@ -145,4 +323,48 @@ pub fn render_matrix_rain(frame: &mut Frame, state: &MatrixRainState, area: Rect
             }
         }
     }
+
+    if let Some(reveal) = &state.reveal {
+        render_reveal(buf, state, reveal, inner);
+    }
+}
+
+/// Draw the hidden message on top of the rain
+fn render_reveal(buf: &mut Buffer, state: &MatrixRainState, reveal: &Reveal, inner: Rect) {
+    let trail = [MATRIX_BODY, MATRIX_TRAIL_1, MATRIX_TRAIL_2, MATRIX_TRAIL_3];
+
+    for (index, (ch, col, row)) in reveal.layout(inner.width, inner.height).into_iter().enumerate() {
+        if col >= inner.width || row >= inner.height {
+            continue;
+        }
+        let x = inner.x + col;
+
+        match reveal.glyph_state(index) {
+            GlyphState::Seeking { distance } => {
+                // A drop falls down the column and stops on the glyph's cell
+                let head = row as i32 - (distance / 2) as i32;
+                for (i, &color) in std::iter::once(&MATRIX_HEAD).chain(trail.iter()).enumerate() {
+                    let y = head - i as i32;
+                    if y >= 0 && y < row as i32 {
+                        let glyph = state.char_set[(index + y as usize + distance as usize) % state.char_set.len()];
+                        buf[(x, inner.y + y as u16)].set_char(glyph).set_fg(color);
+                    }
+                }
+            }
+            GlyphState::Locked { age } => {
+                let color = if age < 6 { TEXT_HIGHLIGHT } else { MATRIX_HEAD };
+                buf[(x, inner.y + row)]
+                    .set_char(ch)
+                    .set_style(Style::default().fg(color).bold());
+            }
+            GlyphState::Falling { age } => {
+                let y = row + (age / 4) as u16;
+                if y < inner.height {
+                    let color = trail[(age as usize * trail.len() / FALL_TICKS as usize).min(trail.len() - 1)];
+                    buf[(x, inner.y + y)].set_char(ch).set_fg(color);
+                }
+            }
+            GlyphState::Hidden => {}
+        }
+    }
 }
@@ -1,15 +1,28 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, BorderType, Gauge},
+    widgets::{Block, Borders, BorderType, Gauge, Sparkline},
 };
+use std::collections::VecDeque;
 
 use crate::data::system_stats::{format_bytes, SystemStats};
 use crate::ui::theme::*;
 
+const HISTORY_SIZE: usize = 60;
+/// Minimum inner height for the stacked breakdown bar and its legend
+const BREAKDOWN_MIN_HEIGHT: u16 = 6;
+
 pub struct MemoryGaugeState {
     pub used: u64,
     pub total: u64,
     pub percentage: f64,
+    pub available: u64,
+    pub free: u64,
+    /// Page cache and buffers, if the platform exposes them
+    pub cached: Option<u64>,
+    pub buffers: Option<u64>,
+    pub swap_used: u64,
+    pub swap_total: u64,
+    pub history: VecDeque<u64>,
 }
 
 impl MemoryGaugeState {
@@ -18,6 +31,13 @@ impl MemoryGaugeState {
             used: 0,
             total: 0,
             percentage: 0.0,
+            available: 0,
+            free: 0,
+            cached: None,
+            buffers: None,
+            swap_used: 0,
+            swap_total: 0,
+            history: std::iter::repeat_n(0, HISTORY_SIZE).collect(),
         }
     }
 
@@ -25,6 +45,24 @@ impl MemoryGaugeState {
         self.used = stats.memory_used;
         self.total = stats.memory_total;
         self.percentage = stats.memory_percentage();
+        self.available = stats.memory_available;
+        self.free = stats.memory_free;
+        self.cached = stats.memory_cached;
+        self.buffers = stats.memory_buffers;
+        self.swap_used = stats.swap_used;
+        self.swap_total = stats.swap_total;
+
+        if self.history.len() >= HISTORY_SIZE {
+            self.history.pop_front();
+        }
+        self.history.push_back(self.percentage as u64);
+    }
+
+    pub fn swap_percentage(&self) -> f64 {
+        if self.swap_total == 0 {
+            return 0.0;
+        }
+        (self.swap_used as f64 / self.swap_total as f64) * 100.0
     }
 }
 
@@ -49,14 +87,24 @@ pub fn render_memory_gauge(frame: &mut Frame, state: &MemoryGaugeState, area: Re
         return;
     }
 
+    // Extra rows as the panel grows: swap, then the breakdown, then history
+    let show_swap = inner.height >= 3;
+    let show_breakdown = inner.height >= BREAKDOWN_MIN_HEIGHT;
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(if show_swap { 1 } else { 0 }),
+            Constraint::Length(if show_breakdown { 2 } else { 0 }),
+            Constraint::Min(0),
+        ])
         .split(inner);
 
     // Main gauge
     let gauge = Gauge::default()
-        .ratio(state.percentage / 100.0)
+        .ratio((state.percentage / 100.0).clamp(0.0, 1.0))
         .gauge_style(Style::default().fg(gauge_color(state.percentage)).bg(DARK_BG))
         .label(format!(
             "{} / {} ({:.1}%)",
@@ -67,21 +115,109 @@ pub fn render_memory_gauge(frame: &mut Frame, state: &MemoryGaugeState, area: Re
 
     frame.render_widget(gauge, chunks[0]);
 
-    // Info line
-    if chunks[1].height > 0 {
-        let info = Line::from(vec![
-            Span::styled("Used: ", Style::default().fg(TEXT_DIM)),
-            Span::styled(format_bytes(state.used), Style::default().fg(NEON_MAGENTA)),
-            Span::styled("  Free: ", Style::default().fg(TEXT_DIM)),
-            Span::styled(
-                format_bytes(state.total.saturating_sub(state.used)),
-                Style::default().fg(NEON_GREEN),
-            ),
-        ]);
-
-        frame.render_widget(
-            ratatui::widgets::Paragraph::new(info),
-            chunks[1],
-        );
+    // Info line; used memory is already in the gauge label
+    let info = Line::from(vec![
+        Span::styled("Avail: ", Style::default().fg(TEXT_DIM)),
+        Span::styled(format_bytes(state.available), Style::default().fg(NEON_GREEN)),
+        Span::styled("  Free: ", Style::default().fg(TEXT_DIM)),
+        Span::styled(format_bytes(state.free), Style::default().fg(NEON_CYAN)),
+    ]);
+    frame.render_widget(info, chunks[1]);
+
+    if show_swap {
+        render_swap(frame, state, chunks[2]);
+    }
+
+    if show_breakdown {
+        render_breakdown(frame, state, chunks[3]);
     }
+
+    if chunks[4].height > 0 {
+        let data: Vec<u64> = state.history.iter().copied().collect();
+        let visible = &data[data.len().saturating_sub(chunks[4].width as usize)..];
+        let sparkline = Sparkline::default()
+            .data(visible)
+            .max(100)
+            .style(Style::default().fg(NEON_MAGENTA));
+        frame.render_widget(sparkline, chunks[4]);
+    }
+}
+
+/// Swap usage as a label and a thin bar
+fn render_swap(frame: &mut Frame, state: &MemoryGaugeState, area: Rect) {
+    let label = Span::styled("Swap ", Style::default().fg(TEXT_DIM));
+    if state.swap_total == 0 {
+        frame.render_widget(Line::from(vec![label, Span::styled("none", Style::default().fg(BORDER_DIM))]), area);
+        return;
+    }
+
+    let percentage = state.swap_percentage();
+    let value = format!(" {} / {}", format_bytes(state.swap_used), format_bytes(state.swap_total));
+    let bar_width = (area.width as usize).saturating_sub(5 + value.chars().count());
+    let filled = ((percentage / 100.0) * bar_width as f64).round() as usize;
+
+    frame.render_widget(
+        Line::from(vec![
+            label,
+            Span::styled("━".repeat(filled.min(bar_width)), Style::default().fg(gauge_color(percentage))),
+            Span::styled("━".repeat(bar_width - filled.min(bar_width)), Style::default().fg(BORDER_DIM)),
+            Span::styled(value, Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        area,
+    );
+}
+
+/// Stacked bar of used, buffers, cache and free memory with a legend
+fn render_breakdown(frame: &mut Frame, state: &MemoryGaugeState, area: Rect) {
+    let mut segments = vec![("used", state.used, NEON_MAGENTA)];
+    if let Some(buffers) = state.buffers {
+        segments.push(("buffers", buffers, NEON_CYAN));
+    }
+    if let Some(cached) = state.cached {
+        segments.push(("cache", cached, NEON_YELLOW));
+    }
+    let accounted: u64 = segments.iter().map(|&(_, bytes, _)| bytes).sum();
+    segments.push(("free", state.total.saturating_sub(accounted), BORDER_DIM));
+
+    // Cell widths, never more than the bar in total
+    let width = area.width as usize;
+    let total = state.total.max(1) as f64;
+    let mut remaining = width;
+    let mut bar = Vec::new();
+    for (i, &(_, bytes, color)) in segments.iter().enumerate() {
+        let cells = if i == segments.len() - 1 {
+            remaining
+        } else {
+            ((bytes as f64 / total) * width as f64).round() as usize
+        };
+        let cells = cells.min(remaining);
+        remaining -= cells;
+        bar.push(Span::styled("█".repeat(cells), Style::default().fg(color)));
+    }
+
+    // Legend with sizes, or just the names if that does not fit
+    let legend = |with_sizes: bool| -> Line {
+        Line::from(
+            segments
+                .iter()
+                .flat_map(|&(name, bytes, color)| {
+                    let text = if with_sizes {
+                        format!("{name} {}  ", format_bytes(bytes))
+                    } else {
+                        format!("{name}  ")
+                    };
+                    [
+                        Span::styled("■ ", Style::default().fg(color)),
+                        Span::styled(text, Style::default().fg(TEXT_DIM)),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        )
+    };
+    let mut legend_line = legend(true);
+    if legend_line.width() > width {
+        legend_line = legend(false);
+    }
+
+    frame.render_widget(Text::from(vec![Line::from(bar), legend_line]), area);
 }
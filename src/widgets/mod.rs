@@ -1,11 +1,17 @@
+pub mod big_text;
 pub mod clock;
 pub mod countdown_timer;
 pub mod cpu_gauge;
+pub mod disk_monitor;
 pub mod fake_logs;
+pub mod globe;
 pub mod hex_dump;
 pub mod matrix_rain;
 pub mod memory_gauge;
 pub mod network_monitor;
+pub mod process_table;
 pub mod progress_bars;
+pub mod self_destruct;
+pub mod sensors;
 pub mod source_code;
 pub mod world_map;
@@ -4,50 +4,175 @@ use ratatui::{
 };
 use std::collections::VecDeque;
 
-use crate::data::system_stats::{format_bytes_per_sec, SystemStats};
+use crate::config::{NetworkConfig, RateUnit, UnitPrefix};
+use crate::data::system_stats::{format_amount, format_bytes_per_sec, InterfaceFilter, InterfaceReading, SystemStats};
 use crate::ui::theme::*;
 
 const HISTORY_SIZE: usize = 30;
 
-pub struct NetworkMonitorState {
+/// Traffic history of one interface, or of all shown interfaces combined
+pub struct InterfaceTraffic {
+    pub name: String,
     pub rx_rate: u64,
     pub tx_rate: u64,
     pub rx_history: VecDeque<u64>,
     pub tx_history: VecDeque<u64>,
+    /// Bytes since start
+    pub rx_total: u64,
+    pub tx_total: u64,
+    /// Highest rates since start
+    pub rx_peak: u64,
+    pub tx_peak: u64,
+    /// Interface counters when first seen, so totals start at zero
+    rx_baseline: u64,
+    tx_baseline: u64,
 }
 
-impl NetworkMonitorState {
-    pub fn new() -> Self {
-        let mut rx_history = VecDeque::with_capacity(HISTORY_SIZE);
-        let mut tx_history = VecDeque::with_capacity(HISTORY_SIZE);
-
-        for _ in 0..HISTORY_SIZE {
-            rx_history.push_back(0);
-            tx_history.push_back(0);
-        }
+impl InterfaceTraffic {
+    fn new(name: &str) -> Self {
+        let history: VecDeque<u64> = std::iter::repeat_n(0, HISTORY_SIZE).collect();
 
         Self {
+            name: name.to_string(),
             rx_rate: 0,
             tx_rate: 0,
-            rx_history,
-            tx_history,
+            rx_history: history.clone(),
+            tx_history: history,
+            rx_total: 0,
+            tx_total: 0,
+            rx_peak: 0,
+            tx_peak: 0,
+            rx_baseline: 0,
+            tx_baseline: 0,
         }
     }
 
-    pub fn update(&mut self, stats: &SystemStats) {
-        self.rx_rate = stats.network_rx;
-        self.tx_rate = stats.network_tx;
+    fn from_reading(reading: &InterfaceReading) -> Self {
+        Self {
+            rx_baseline: reading.total_rx,
+            tx_baseline: reading.total_tx,
+            ..Self::new(&reading.name)
+        }
+    }
+
+    fn push(&mut self, rx: u64, tx: u64) {
+        self.rx_rate = rx;
+        self.tx_rate = tx;
+        self.rx_peak = self.rx_peak.max(rx);
+        self.tx_peak = self.tx_peak.max(tx);
 
         // Update histories
         if self.rx_history.len() >= HISTORY_SIZE {
             self.rx_history.pop_front();
         }
-        self.rx_history.push_back(self.rx_rate);
+        self.rx_history.push_back(rx);
 
         if self.tx_history.len() >= HISTORY_SIZE {
             self.tx_history.pop_front();
         }
-        self.tx_history.push_back(self.tx_rate);
+        self.tx_history.push_back(tx);
+    }
+}
+
+pub struct NetworkMonitorState {
+    /// All shown interfaces combined
+    pub combined: InterfaceTraffic,
+    /// Shown interfaces, sorted by name
+    pub interfaces: Vec<InterfaceTraffic>,
+    /// 0 for the combined traffic, otherwise an index into `interfaces` plus one
+    pub selected: usize,
+    pub unit: RateUnit,
+    pub prefix: UnitPrefix,
+    pub log_scale: bool,
+    filter: InterfaceFilter,
+}
+
+impl NetworkMonitorState {
+    pub fn new() -> Self {
+        Self::with_config(&NetworkConfig::default())
+    }
+
+    pub fn with_config(config: &NetworkConfig) -> Self {
+        Self {
+            combined: InterfaceTraffic::new("ALL"),
+            interfaces: Vec::new(),
+            selected: 0,
+            unit: config.units,
+            prefix: config.prefix,
+            log_scale: config.log_scale,
+            filter: InterfaceFilter::with_config(config),
+        }
+    }
+
+    pub fn update(&mut self, stats: &SystemStats) {
+        let selected_name = self.selected_traffic().name.clone();
+        let mut previous = std::mem::take(&mut self.interfaces);
+        let (mut rx_sum, mut tx_sum, mut rx_total, mut tx_total) = (0u64, 0u64, 0u64, 0u64);
+
+        for reading in &stats.interfaces {
+            if !self.filter.shows(&reading.name) {
+                continue;
+            }
+
+            let mut traffic = match previous.iter().position(|t| t.name == reading.name) {
+                Some(index) => previous.swap_remove(index),
+                None => InterfaceTraffic::from_reading(reading),
+            };
+            traffic.push(reading.rx, reading.tx);
+            traffic.rx_total = reading.total_rx.saturating_sub(traffic.rx_baseline);
+            traffic.tx_total = reading.total_tx.saturating_sub(traffic.tx_baseline);
+
+            rx_sum = rx_sum.saturating_add(reading.rx);
+            tx_sum = tx_sum.saturating_add(reading.tx);
+            rx_total = rx_total.saturating_add(traffic.rx_total);
+            tx_total = tx_total.saturating_add(traffic.tx_total);
+            self.interfaces.push(traffic);
+        }
+        self.combined.push(rx_sum, tx_sum);
+        self.combined.rx_total = rx_total;
+        self.combined.tx_total = tx_total;
+
+        // Keep the selection on the same interface if it is still there
+        self.selected = self
+            .interfaces
+            .iter()
+            .position(|traffic| traffic.name == selected_name)
+            .map_or(0, |index| index + 1);
+    }
+
+    pub fn selected_traffic(&self) -> &InterfaceTraffic {
+        match self.selected {
+            0 => &self.combined,
+            index => &self.interfaces[index - 1],
+        }
+    }
+
+    /// Show the next (or previous) interface, with the combined view first
+    pub fn cycle(&mut self, forward: bool) {
+        let count = self.interfaces.len() + 1;
+        self.selected = if forward {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+    }
+
+    pub fn toggle_log_scale(&mut self) {
+        self.log_scale = !self.log_scale;
+    }
+
+    pub fn toggle_unit(&mut self) {
+        self.unit = match self.unit {
+            RateUnit::Bytes => RateUnit::Bits,
+            RateUnit::Bits => RateUnit::Bytes,
+        };
+    }
+
+    pub fn toggle_prefix(&mut self) {
+        self.prefix = match self.prefix {
+            UnitPrefix::Iec => UnitPrefix::Si,
+            UnitPrefix::Si => UnitPrefix::Iec,
+        };
     }
 }
 
@@ -58,12 +183,19 @@ impl Default for NetworkMonitorState {
 }
 
 pub fn render_network_monitor(frame: &mut Frame, state: &NetworkMonitorState, area: Rect) {
+    let traffic = state.selected_traffic();
+    let mut view = traffic.name.clone();
+    if state.log_scale {
+        view.push_str(" · LOG");
+    }
+
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(BORDER_DIM))
         .title(" NETWORK ")
-        .title_style(Style::default().fg(NEON_ORANGE).bold());
+        .title_style(Style::default().fg(NEON_ORANGE).bold())
+        .title(Line::styled(format!(" {view} "), Style::default().fg(TEXT_DIM)).right_aligned());
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -83,34 +215,60 @@ pub fn render_network_monitor(frame: &mut Frame, state: &NetworkMonitorState, ar
         .split(inner);
 
     // TX (upload)
-    let tx_label = Line::from(vec![
-        Span::styled("▲ TX: ", Style::default().fg(NEON_ORANGE)),
-        Span::styled(
-            format_bytes_per_sec(state.tx_rate),
-            Style::default().fg(TEXT_PRIMARY),
-        ),
-    ]);
-    frame.render_widget(ratatui::widgets::Paragraph::new(tx_label), chunks[0]);
-
-    let tx_data: Vec<u64> = state.tx_history.iter().copied().collect();
-    let tx_sparkline = Sparkline::default()
-        .data(&tx_data)
-        .style(Style::default().fg(NEON_ORANGE));
-    frame.render_widget(tx_sparkline, chunks[1]);
+    let tx = [traffic.tx_rate, traffic.tx_peak, traffic.tx_total];
+    let tx_label = rate_line(state, "▲ TX: ", NEON_ORANGE, tx, inner.width);
+    frame.render_widget(tx_label, chunks[0]);
+    render_history(frame, state, &traffic.tx_history, NEON_ORANGE, chunks[1]);
 
     // RX (download)
-    let rx_label = Line::from(vec![
-        Span::styled("▼ RX: ", Style::default().fg(NEON_CYAN)),
-        Span::styled(
-            format_bytes_per_sec(state.rx_rate),
-            Style::default().fg(TEXT_PRIMARY),
-        ),
+    let rx = [traffic.rx_rate, traffic.rx_peak, traffic.rx_total];
+    let rx_label = rate_line(state, "▼ RX: ", NEON_CYAN, rx, inner.width);
+    frame.render_widget(rx_label, chunks[2]);
+    render_history(frame, state, &traffic.rx_history, NEON_CYAN, chunks[3]);
+}
+
+/// Current rate, followed by the peak and total if they fit
+fn rate_line(
+    state: &NetworkMonitorState,
+    label: &'static str,
+    color: Color,
+    [rate, peak, total]: [u64; 3],
+    width: u16,
+) -> Line<'static> {
+    let mut line = Line::from(vec![
+        Span::styled(label, Style::default().fg(color)),
+        Span::styled(format_bytes_per_sec(rate, state.unit, state.prefix), Style::default().fg(TEXT_PRIMARY)),
     ]);
-    frame.render_widget(ratatui::widgets::Paragraph::new(rx_label), chunks[2]);
 
-    let rx_data: Vec<u64> = state.rx_history.iter().copied().collect();
-    let rx_sparkline = Sparkline::default()
-        .data(&rx_data)
-        .style(Style::default().fg(NEON_CYAN));
-    frame.render_widget(rx_sparkline, chunks[3]);
+    let extra = [
+        Span::styled("  pk ", Style::default().fg(TEXT_DIM)),
+        Span::styled(format_bytes_per_sec(peak, state.unit, state.prefix), Style::default().fg(TEXT_PRIMARY)),
+        Span::styled("  Σ ", Style::default().fg(TEXT_DIM)),
+        Span::styled(format_amount(total, state.unit, state.prefix), Style::default().fg(TEXT_PRIMARY)),
+    ];
+    if line.width() + extra.iter().map(Span::width).sum::<usize>() <= width as usize {
+        line.spans.extend(extra);
+    }
+
+    line
+}
+
+fn render_history(
+    frame: &mut Frame,
+    state: &NetworkMonitorState,
+    history: &VecDeque<u64>,
+    color: Color,
+    area: Rect,
+) {
+    // On the log scale each doubling of the rate adds the same height
+    let data: Vec<u64> = if state.log_scale {
+        history.iter().map(|&rate| ((rate as f64 + 1.0).log2() * 16.0) as u64).collect()
+    } else {
+        history.iter().copied().collect()
+    };
+
+    let sparkline = Sparkline::default()
+        .data(&data)
+        .style(Style::default().fg(color));
+    frame.render_widget(sparkline, area);
 }
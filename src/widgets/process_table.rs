@@ -0,0 +1,211 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, BorderType, Cell, Row, Table},
+};
+use std::collections::HashMap;
+
+use crate::config::{ProcessConfig, ProcessSort};
+use crate::data::system_stats::{format_bytes, ProcessReading, SystemStats};
+use crate::ui::theme::*;
+
+/// New processes announced by name per refresh in dramatic mode
+const MAX_ANNOUNCED: usize = 3;
+/// Narrowest table that still gets the user column
+const USER_COLUMN_MIN_WIDTH: u16 = 44;
+
+/// How a process changed since the previous refresh
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessStatus {
+    Running,
+    New,
+    Gone,
+}
+
+pub struct ProcessRow {
+    pub process: ProcessReading,
+    pub status: ProcessStatus,
+}
+
+pub struct ProcessTableState {
+    /// Running processes and the ones that just ended, sorted
+    pub rows: Vec<ProcessRow>,
+    pub sort: ProcessSort,
+    pub descending: bool,
+    dramatic: bool,
+    /// Has a first list been seen? Everything is new before that
+    started: bool,
+    /// Log lines for new processes since the last `take_announcements`
+    announcements: Vec<String>,
+}
+
+impl ProcessTableState {
+    pub fn new() -> Self {
+        Self::with_config(&ProcessConfig::default())
+    }
+
+    pub fn with_config(config: &ProcessConfig) -> Self {
+        Self {
+            rows: Vec::new(),
+            sort: config.sort,
+            descending: default_descending(config.sort),
+            dramatic: config.dramatic,
+            started: false,
+            announcements: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, stats: &SystemStats) {
+        let mut previous: HashMap<u32, ProcessReading> = std::mem::take(&mut self.rows)
+            .into_iter()
+            .filter(|row| row.status != ProcessStatus::Gone)
+            .map(|row| (row.process.pid, row.process))
+            .collect();
+
+        let mut new = Vec::new();
+        for process in &stats.processes {
+            let status = if previous.remove(&process.pid).is_some() || !self.started {
+                ProcessStatus::Running
+            } else {
+                new.push(process);
+                ProcessStatus::New
+            };
+            self.rows.push(ProcessRow { process: process.clone(), status });
+        }
+        self.rows.extend(previous.into_values().map(|process| ProcessRow {
+            process,
+            status: ProcessStatus::Gone,
+        }));
+
+        if self.dramatic {
+            self.announcements.extend(new.iter().take(MAX_ANNOUNCED).map(|process| {
+                format!(
+                    "NEW PROCESS DETECTED: {} (pid {}, user {})",
+                    process.name, process.pid, process.user
+                )
+            }));
+            if new.len() > MAX_ANNOUNCED {
+                self.announcements.push(format!("...and {} more new processes", new.len() - MAX_ANNOUNCED));
+            }
+        }
+
+        self.started = true;
+        self.sort_rows();
+    }
+
+    /// Sort by a column; choosing the current column again reverses the order
+    pub fn sort_by(&mut self, column: ProcessSort) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            self.descending = default_descending(column);
+        }
+        self.sort_rows();
+    }
+
+    fn sort_rows(&mut self) {
+        let sort = self.sort;
+        self.rows.sort_by(|a, b| {
+            let (a, b) = (&a.process, &b.process);
+            let order = match sort {
+                ProcessSort::Pid => a.pid.cmp(&b.pid),
+                ProcessSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                ProcessSort::Cpu => a.cpu.total_cmp(&b.cpu),
+                ProcessSort::Memory => a.memory.cmp(&b.memory),
+                ProcessSort::User => a.user.cmp(&b.user),
+            };
+            order.then(a.pid.cmp(&b.pid))
+        });
+        if self.descending {
+            self.rows.reverse();
+        }
+    }
+
+    /// Log lines for processes that appeared since the last call
+    pub fn take_announcements(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.announcements)
+    }
+
+    fn count(&self, status: ProcessStatus) -> usize {
+        self.rows.iter().filter(|row| row.status == status).count()
+    }
+}
+
+impl Default for ProcessTableState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Busiest first for usage columns, alphabetical for the others
+fn default_descending(column: ProcessSort) -> bool {
+    matches!(column, ProcessSort::Cpu | ProcessSort::Memory)
+}
+
+pub fn render_process_table(frame: &mut Frame, state: &ProcessTableState, area: Rect) {
+    let running = state.rows.len() - state.count(ProcessStatus::Gone);
+    let mut summary = format!(" {running} TASKS ");
+    let (new, gone) = (state.count(ProcessStatus::New), state.count(ProcessStatus::Gone));
+    if new + gone > 0 {
+        summary.push_str(&format!("· +{new} -{gone} "));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_DIM))
+        .title(" PROCESSES ")
+        .title_style(Style::default().fg(NEON_YELLOW).bold())
+        .title(Line::styled(summary, Style::default().fg(TEXT_DIM)).right_aligned());
+
+    let show_user = block.inner(area).width >= USER_COLUMN_MIN_WIDTH;
+    let mut columns = vec![
+        (ProcessSort::Pid, "PID", Constraint::Length(7)),
+        (ProcessSort::Name, "NAME", Constraint::Fill(1)),
+        (ProcessSort::Cpu, "CPU%", Constraint::Length(6)),
+        (ProcessSort::Memory, "MEM", Constraint::Length(9)),
+    ];
+    if show_user {
+        columns.push((ProcessSort::User, "USER", Constraint::Length(10)));
+    }
+
+    let header = Row::new(columns.iter().map(|&(column, title, _)| {
+        if column == state.sort {
+            let arrow = if state.descending { "▼" } else { "▲" };
+            Cell::from(format!("{title}{arrow}")).style(Style::default().fg(NEON_YELLOW).bold())
+        } else {
+            Cell::from(title).style(Style::default().fg(TEXT_DIM))
+        }
+    }));
+
+    let rows = state.rows.iter().map(|row| {
+        let process = &row.process;
+        let style = match row.status {
+            ProcessStatus::Running => Style::default().fg(TEXT_PRIMARY),
+            ProcessStatus::New => Style::default().fg(NEON_GREEN).bold(),
+            ProcessStatus::Gone => Style::default().fg(NEON_RED).crossed_out(),
+        };
+        let cpu_style = match row.status {
+            ProcessStatus::Running => Style::default().fg(gauge_color(process.cpu)),
+            _ => style,
+        };
+
+        let mut cells = vec![
+            Cell::from(process.pid.to_string()),
+            Cell::from(process.name.clone()),
+            Cell::from(format!("{:>5.1}", process.cpu)).style(cpu_style),
+            Cell::from(format!("{:>8}", format_bytes(process.memory))),
+        ];
+        if show_user {
+            cells.push(Cell::from(process.user.clone()));
+        }
+        Row::new(cells).style(style)
+    });
+
+    let table = Table::new(rows, columns.iter().map(|&(_, _, width)| width))
+        .header(header)
+        .column_spacing(1)
+        .block(block);
+
+    frame.render_widget(table, area);
+}
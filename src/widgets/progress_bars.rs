@@ -1,79 +1,489 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, BorderType, Gauge},
+    widgets::{
+        canvas::{Canvas, Circle, Points},
+        Block, Borders, BorderType, Gauge,
+    },
 };
 use rand::Rng;
+use regex::Regex;
+use std::{f64::consts::TAU, io, path::Path};
 
+use crate::config::{ProgressBarConfig, ProgressConfig, ProgressStyle};
+use crate::data::progress_feed::{FeedUpdate, ProgressFeed};
+use crate::data::system_stats::{format_bytes, parse_bytes};
 use crate::ui::theme::*;
+use crate::TICKS_PER_SECOND;
 
-const NUM_BARS: usize = 4;
+/// Colors of the slots in turn, for bars without their own
+const SLOT_COLORS: [Color; 6] = [NEON_CYAN, NEON_MAGENTA, NEON_GREEN, NEON_ORANGE, NEON_PURPLE, NEON_YELLOW];
+/// Ticks a bar flashes when it completes
+const COMPLETE_FLASH: u8 = 30;
+/// Progress in command output when no pattern is given: a number before "%"
+const DEFAULT_PATTERN: &str = r"(\d+(?:\.\d+)?)\s*%";
+/// Weight of the newest tick in the smoothed progress rate
+const RATE_SMOOTHING: f64 = 0.02;
+/// Chance per tick that a random operation stalls
+const STALL_CHANCE: f64 = 0.002;
+/// Chance that a finished stage of a random operation fails and is retried
+const FAIL_CHANCE: f64 = 0.06;
+/// Ticks spent retrying a failed stage
+const RETRY_TICKS: u32 = 120;
+/// Most rows a bar's meter grows to
+const MAX_METER_ROWS: u16 = 4;
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+/// Quarter-filled circles for a radial meter on one row
+const RADIAL_GLYPHS: [&str; 5] = ["○", "◔", "◑", "◕", "●"];
+/// Cells from one packet to the next
+const PACKET_SPACING: usize = 6;
+
+/// A kind of operation for the random bars, with its stages in order
+struct Operation {
+    name: &'static str,
+    stages: &'static [&'static str],
+}
+
+const OPERATIONS: &[Operation] = &[
+    Operation { name: "DECRYPTING", stages: &["HANDSHAKE", "KEY EXCHANGE", "DECRYPT", "VERIFY"] },
+    Operation { name: "UPLOADING", stages: &["CONNECT", "AUTHENTICATE", "TRANSFER", "CHECKSUM"] },
+    Operation { name: "COMPILING", stages: &["PARSE", "TYPE CHECK", "OPTIMIZE", "LINK"] },
+    Operation { name: "ANALYZING", stages: &["SAMPLE", "CORRELATE", "CLASSIFY", "REPORT"] },
+    Operation { name: "ENCRYPTING", stages: &["KEY DERIVATION", "ENCRYPT", "SIGN"] },
+    Operation { name: "DOWNLOADING", stages: &["RESOLVE", "CONNECT", "TRANSFER", "VERIFY"] },
+    Operation { name: "SCANNING", stages: &["DISCOVER", "FINGERPRINT", "PROBE"] },
+    Operation { name: "INJECTING", stages: &["LOCATE", "PATCH", "HOOK", "CONFIRM"] },
+    Operation { name: "EXTRACTING", stages: &["OPEN ARCHIVE", "INFLATE", "WRITE"] },
+    Operation { name: "DEPLOYING", stages: &["BUILD IMAGE", "PUSH", "ROLL OUT", "HEALTH CHECK"] },
+    Operation { name: "SYNCING", stages: &["DIFF", "TRANSFER", "COMMIT"] },
+    Operation { name: "HASHING", stages: &["READ", "DIGEST", "COMPARE"] },
+    Operation { name: "CRACKING", stages: &["DICTIONARY", "RAINBOW TABLES", "BRUTE FORCE"] },
+    Operation { name: "TUNNELING", stages: &["ROUTE", "HANDSHAKE", "ENCAPSULATE", "ESTABLISH"] },
+];
+
+/// Where a bar's progress comes from
+enum BarSource {
+    /// Random fills, starting over with a new operation
+    Random,
+    /// A feed, by index, and the label it reports this bar under
+    Feed(usize, Option<String>),
+    /// Fills over a number of ticks
+    Timer { elapsed: u64, total: u64 },
+    /// Set from the prompt or a scenario
+    Manual,
+}
 
 struct ProgressBar {
-    label: &'static str,
+    label: String,
+    /// Keep the configured label whatever the feed reports
+    fixed_label: bool,
+    /// Named parts of the operation, splitting the bar evenly
+    stages: Vec<String>,
     progress: f64,
     speed: f64,
+    /// Bytes moved by the whole operation, for the throughput
+    size: Option<f64>,
+    /// Smoothed progress per tick, for the throughput and estimate
+    rate: f64,
+    /// Progress at the last tick, for the rate
+    seen: f64,
     color: Color,
+    style: ProgressStyle,
+    /// Animation ticks, standing still while the bar does
+    phase: u64,
     complete_flash: u8,
+    failed: bool,
+    /// Ticks left in a stall
+    stalled: u32,
+    /// Ticks left retrying the failed stage
+    retrying: u32,
+    source: BarSource,
 }
 
 impl ProgressBar {
-    fn new(label: &'static str, color: Color) -> Self {
-        let mut rng = rand::thread_rng();
-        Self {
-            label,
-            progress: rng.gen_range(0.0..0.5),
-            speed: rng.gen_range(0.002..0.015),
+    fn new(operation: &Operation, color: Color, style: ProgressStyle) -> Self {
+        let mut bar = Self {
+            label: String::new(),
+            fixed_label: false,
+            stages: Vec::new(),
+            progress: 0.0,
+            speed: 0.0,
+            size: None,
+            rate: 0.0,
+            seen: 0.0,
             color,
+            style,
+            phase: 0,
             complete_flash: 0,
-        }
+            failed: false,
+            stalled: 0,
+            retrying: 0,
+            source: BarSource::Random,
+        };
+        bar.start(operation);
+        bar.progress = rand::thread_rng().gen_range(0.0..0.5);
+        bar.seen = bar.progress;
+        bar.rate = bar.speed;
+        bar
+    }
+
+    /// A bar following the configured source; new feeds are added to `feeds`
+    fn with_config(
+        config: &ProgressBarConfig,
+        color: Color,
+        style: ProgressStyle,
+        feeds: &mut Vec<ProgressFeed>,
+    ) -> Result<Self, String> {
+        let mut size = None;
+        let (source, label) = match (&config.file, &config.pipe, &config.command, config.seconds) {
+            (Some(file), None, None, None) => {
+                let text = config.size.as_deref().ok_or("a file bar needs a `size`")?;
+                let bytes = parse_bytes(text)?;
+                if bytes <= 0.0 {
+                    return Err(format!("invalid size '{text}'"));
+                }
+                size = Some(bytes);
+                feeds.push(ProgressFeed::file(file.clone(), bytes));
+                (BarSource::Feed(feeds.len() - 1, None), file_label(file))
+            }
+            (None, Some(pipe), None, None) => {
+                feeds.push(ProgressFeed::lines(pipe.clone()));
+                let label = if pipe.as_os_str() == "-" { "STDIN".to_string() } else { file_label(pipe) };
+                (BarSource::Feed(feeds.len() - 1, None), label)
+            }
+            (None, None, Some(command), None) => {
+                let pattern = config.pattern.as_deref().unwrap_or(DEFAULT_PATTERN);
+                let pattern = Regex::new(pattern).map_err(|e| format!("invalid pattern '{pattern}': {e}"))?;
+                feeds.push(ProgressFeed::command(command, pattern).map_err(|e| format!("{command}: {e}"))?);
+                let program = command.split_whitespace().next().unwrap_or("COMMAND");
+                (BarSource::Feed(feeds.len() - 1, None), file_label(Path::new(program)))
+            }
+            (None, None, None, Some(seconds)) if seconds > 0.0 => {
+                let total = ((seconds * TICKS_PER_SECOND) as u64).max(1);
+                (BarSource::Timer { elapsed: 0, total }, "TIMER".to_string())
+            }
+            (None, None, None, Some(seconds)) => return Err(format!("invalid seconds {seconds}")),
+            _ => return Err("a bar needs one of `file`, `pipe`, `command` or `seconds`".to_string()),
+        };
+
+        Ok(Self {
+            label: config.label.clone().unwrap_or(label),
+            fixed_label: config.label.is_some(),
+            stages: config.stages.clone(),
+            progress: 0.0,
+            speed: 0.0,
+            size,
+            rate: 0.0,
+            seen: 0.0,
+            color: config.color.map_or(color, accent_color),
+            style: config.style.unwrap_or(style),
+            phase: 0,
+            complete_flash: 0,
+            failed: false,
+            stalled: 0,
+            retrying: 0,
+            source,
+        })
     }
 
     fn tick(&mut self) {
+        self.advance();
+        // Feeds move their bars between ticks
+        let moved = (self.progress - self.seen).max(0.0);
+        self.seen = self.progress;
+        self.rate += (moved - self.rate) * RATE_SMOOTHING;
+
+        if self.stalled == 0 && self.retrying == 0 && !self.failed && !self.is_complete() {
+            self.phase += 1;
+        }
+    }
+
+    fn advance(&mut self) {
         if self.complete_flash > 0 {
             self.complete_flash -= 1;
-            if self.complete_flash == 0 {
-                self.reset();
+            if self.complete_flash == 0 && self.is_random() {
+                self.start(random_operation());
             }
             return;
         }
 
-        self.progress += self.speed;
+        match &mut self.source {
+            BarSource::Random => self.advance_random(),
+            BarSource::Timer { elapsed, total } => {
+                *elapsed = (*elapsed + 1).min(*total);
+                let fraction = *elapsed as f64 / *total as f64;
+                self.set_progress(fraction);
+            }
+            BarSource::Feed(..) | BarSource::Manual => {}
+        }
+    }
+
+    /// Uneven progress with the odd stall, and stages that sometimes fail
+    /// and start over
+    fn advance_random(&mut self) {
+        let mut rng = rand::thread_rng();
+
+        if self.retrying > 0 {
+            self.retrying -= 1;
+            if self.retrying == 0 {
+                self.progress = self.stage_start(self.stage());
+            }
+            return;
+        }
+        if self.stalled > 0 {
+            self.stalled -= 1;
+            return;
+        }
+        if rng.gen_bool(STALL_CHANCE) {
+            self.stalled = rng.gen_range(60..240);
+            return;
+        }
+
+        let stage = self.stage();
+        self.progress = (self.progress + self.speed * rng.gen_range(0.4..1.6)).min(1.0);
 
-        if self.progress >= 1.0 {
-            self.progress = 1.0;
-            self.complete_flash = 30; // Flash for 30 ticks
+        if self.progress >= 1.0 || self.stage() != stage {
+            if rng.gen_bool(FAIL_CHANCE) {
+                // Hold at the end of the failed stage while retrying it
+                self.progress = self.stage_start(stage + 1).min(1.0) - f64::EPSILON;
+                self.retrying = RETRY_TICKS;
+            } else if self.progress >= 1.0 {
+                self.complete_flash = COMPLETE_FLASH;
+            }
         }
     }
 
-    fn reset(&mut self) {
+    /// Take over a random bar for another source, dropping its operation
+    fn take_over(&mut self, source: BarSource, label: String) {
+        self.progress = 0.0;
+        self.seen = 0.0;
+        self.rate = 0.0;
+        self.size = None;
+        self.stages.clear();
+        self.complete_flash = 0;
+        self.stalled = 0;
+        self.retrying = 0;
+        self.source = source;
+        self.label = label;
+    }
+
+    /// Begin a new random operation
+    fn start(&mut self, operation: &Operation) {
         let mut rng = rand::thread_rng();
+        self.label = operation.name.to_string();
+        self.stages = operation.stages.iter().map(|stage| stage.to_string()).collect();
         self.progress = 0.0;
-        self.speed = rng.gen_range(0.002..0.015);
-        self.label = random_operation();
+        self.speed = rng.gen_range(0.0015..0.01);
+        self.size = Some(rng.gen_range(20.0e6..4.0e9));
+        self.stalled = 0;
+        self.retrying = 0;
+    }
+
+    /// Real progress; flashes once on reaching the end, then stays complete
+    fn set_progress(&mut self, fraction: f64) {
+        if fraction >= 1.0 && self.progress < 1.0 {
+            self.complete_flash = COMPLETE_FLASH;
+        }
+        self.progress = fraction;
+        self.failed = false;
+    }
+
+    fn is_random(&self) -> bool {
+        matches!(self.source, BarSource::Random)
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete_flash > 0 || (self.progress >= 1.0 && self.retrying == 0)
+    }
+
+    /// Index of the stage in progress
+    fn stage(&self) -> usize {
+        let count = self.stages.len().max(1);
+        ((self.progress * count as f64) as usize).min(count - 1)
+    }
+
+    fn stage_start(&self, stage: usize) -> f64 {
+        stage as f64 / self.stages.len().max(1) as f64
+    }
+
+    /// "KEY EXCHANGE 2/4", or nothing for a bar without stages
+    fn stage_label(&self) -> Option<String> {
+        let stage = self.stages.get(self.stage())?;
+        Some(format!("{stage} {}/{}", self.stage() + 1, self.stages.len()))
+    }
+
+    /// "12.4 MB/s", for bars that know how much they move
+    fn throughput(&self) -> Option<String> {
+        let size = self.size?;
+        let bytes = if self.stalled > 0 { 0.0 } else { self.rate * size * TICKS_PER_SECOND };
+        Some(format!("{}/s", format_bytes(bytes as u64)))
+    }
+
+    /// Time left at the current rate, as "ETA 01:23"
+    fn eta(&self) -> String {
+        if self.rate <= 1e-7 || self.stalled > 0 || self.failed {
+            return "ETA --:--".to_string();
+        }
+        let seconds = ((1.0 - self.progress) / self.rate / TICKS_PER_SECOND) as u64;
+        match seconds {
+            0..3600 => format!("ETA {:02}:{:02}", seconds / 60, seconds % 60),
+            3600..360_000 => format!("ETA {}h{:02}", seconds / 3600, seconds / 60 % 60),
+            _ => "ETA --:--".to_string(),
+        }
+    }
+
+    /// The state worth calling out, with its color
+    fn status(&self) -> Option<(&'static str, Color)> {
+        if self.failed {
+            Some(("✗ FAILED", STATUS_ERROR))
+        } else if self.retrying > 0 {
+            Some(("⟳ RETRYING", STATUS_ERROR))
+        } else if self.is_complete() {
+            Some(("✓ COMPLETE", STATUS_SUCCESS))
+        } else if self.stalled > 0 {
+            Some(("⏸ STALLED", STATUS_WARNING))
+        } else {
+            None
+        }
+    }
+
+    fn gauge_color(&self) -> Color {
+        if self.failed || self.retrying > 0 {
+            STATUS_ERROR
+        } else if self.complete_flash > 0 {
+            if self.complete_flash % 6 < 3 {
+                STATUS_SUCCESS
+            } else {
+                self.color
+            }
+        } else if self.progress >= 1.0 {
+            STATUS_SUCCESS
+        } else if self.stalled > 0 {
+            STATUS_WARNING
+        } else {
+            self.color
+        }
     }
 }
 
+/// A file or program name as a bar label
+fn file_label(path: &Path) -> String {
+    path.file_name().map_or("FILE".to_string(), |name| name.to_string_lossy().to_uppercase())
+}
+
 pub struct ProgressBarsState {
     bars: Vec<ProgressBar>,
+    feeds: Vec<ProgressFeed>,
 }
 
 impl ProgressBarsState {
     pub fn new() -> Self {
-        let bars = vec![
-            ProgressBar::new("DECRYPTING", NEON_CYAN),
-            ProgressBar::new("UPLOADING", NEON_MAGENTA),
-            ProgressBar::new("COMPILING", NEON_GREEN),
-            ProgressBar::new("ANALYZING", NEON_ORANGE),
-        ];
+        Self::with_config(&ProgressConfig::default()).expect("default progress config is valid")
+    }
+
+    /// The configured bars first, then random operations in the free slots
+    pub fn with_config(config: &ProgressConfig) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+        if config.count == 0 {
+            return Err(invalid("progress: count must be at least 1".to_string()));
+        }
+        if config.bars.len() > config.count {
+            return Err(invalid(format!("progress: {} bars configured, but count is {}", config.bars.len(), config.count)));
+        }
+
+        let mut feeds = Vec::new();
+        let mut bars = Vec::with_capacity(config.count);
+        for (slot, bar) in config.bars.iter().enumerate() {
+            let color = SLOT_COLORS[slot % SLOT_COLORS.len()];
+            let bar = ProgressBar::with_config(bar, color, config.style, &mut feeds)
+                .map_err(|e| invalid(format!("progress bar {}: {e}", slot + 1)))?;
+            bars.push(bar);
+        }
+        for slot in bars.len()..config.count {
+            let operation = &OPERATIONS[slot % OPERATIONS.len()];
+            bars.push(ProgressBar::new(operation, SLOT_COLORS[slot % SLOT_COLORS.len()], config.style));
+        }
 
-        Self { bars }
+        Ok(Self { bars, feeds })
     }
 
     pub fn tick(&mut self) {
+        for index in 0..self.feeds.len() {
+            for update in self.feeds[index].updates() {
+                self.apply(index, update);
+            }
+        }
+
         for bar in &mut self.bars {
             bar.tick();
         }
     }
+
+    /// Move the bars of a feed. Each new label from a pipe takes the feed's
+    /// unclaimed bar, then a random one; with none left it is dropped.
+    fn apply(&mut self, feed: usize, update: FeedUpdate) {
+        let of_feed = |bar: &ProgressBar| matches!(bar.source, BarSource::Feed(index, _) if index == feed);
+
+        match update {
+            FeedUpdate::Progress { label: None, fraction } => {
+                for bar in self.bars.iter_mut().filter(|bar| of_feed(bar)) {
+                    bar.set_progress(fraction);
+                }
+            }
+            FeedUpdate::Progress { label: Some(label), fraction } => {
+                let claimed = |bar: &ProgressBar| {
+                    matches!(&bar.source, BarSource::Feed(index, Some(name)) if *index == feed && *name == label)
+                };
+                let unclaimed = |bar: &ProgressBar| matches!(bar.source, BarSource::Feed(index, None) if index == feed);
+                let bar = match self.bars.iter().position(claimed) {
+                    Some(index) => &mut self.bars[index],
+                    None => {
+                        let free = self.bars.iter().position(unclaimed);
+                        let Some(index) = free.or_else(|| self.bars.iter().position(ProgressBar::is_random)) else {
+                            return;
+                        };
+                        let bar = &mut self.bars[index];
+                        let source = BarSource::Feed(feed, Some(label.clone()));
+                        if bar.is_random() {
+                            bar.take_over(source, label);
+                        } else {
+                            bar.source = source;
+                            if !bar.fixed_label {
+                                bar.label = label;
+                            }
+                        }
+                        bar
+                    }
+                };
+                bar.set_progress(fraction);
+            }
+            FeedUpdate::Finished { success } => {
+                for bar in self.bars.iter_mut().filter(|bar| of_feed(bar)) {
+                    if success {
+                        bar.set_progress(1.0);
+                    } else {
+                        bar.failed = true;
+                        bar.complete_flash = 0;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Set the bar with the label by hand; a random bar takes the label if
+    /// none has it. False if every bar is taken.
+    pub fn set(&mut self, label: &str, fraction: f64) -> bool {
+        let labelled = self.bars.iter().position(|bar| bar.label.eq_ignore_ascii_case(label));
+        let Some(index) = labelled.or_else(|| self.bars.iter().position(ProgressBar::is_random)) else {
+            return false;
+        };
+
+        let bar = &mut self.bars[index];
+        if bar.is_random() {
+            bar.take_over(BarSource::Manual, label.to_string());
+        }
+        bar.set_progress(fraction.clamp(0.0, 1.0));
+        true
+    }
 }
 
 impl Default for ProgressBarsState {
@@ -82,27 +492,9 @@ impl Default for ProgressBarsState {
     }
 }
 
-fn random_operation() -> &'static str {
-    const OPERATIONS: &[&str] = &[
-        "DECRYPTING",
-        "ENCRYPTING",
-        "UPLOADING",
-        "DOWNLOADING",
-        "COMPILING",
-        "ANALYZING",
-        "SCANNING",
-        "INJECTING",
-        "EXTRACTING",
-        "PROCESSING",
-        "DEPLOYING",
-        "SYNCING",
-        "HASHING",
-        "CRACKING",
-        "TUNNELING",
-    ];
-
+fn random_operation() -> &'static Operation {
     let mut rng = rand::thread_rng();
-    OPERATIONS[rng.gen_range(0..OPERATIONS.len())]
+    &OPERATIONS[rng.gen_range(0..OPERATIONS.len())]
 }
 
 pub fn render_progress_bars(frame: &mut Frame, state: &ProgressBarsState, area: Rect) {
@@ -116,41 +508,232 @@ pub fn render_progress_bars(frame: &mut Frame, state: &ProgressBarsState, area:
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    if inner.height < NUM_BARS as u16 * 2 {
+    if inner.height == 0 {
         return;
     }
 
-    let bar_height = inner.height / NUM_BARS as u16;
+    // As many bars as fit, each with a details line when there is room
+    let shown = state.bars.len().min(inner.height as usize);
+    let bar_height = inner.height / shown as u16;
 
-    for (i, bar) in state.bars.iter().enumerate() {
-        let bar_area = Rect {
+    for (i, bar) in state.bars.iter().take(shown).enumerate() {
+        let slot = Rect {
             x: inner.x,
             y: inner.y + (i as u16 * bar_height),
             width: inner.width,
-            height: bar_height.min(2),
+            height: bar_height.min(MAX_METER_ROWS + 1),
         };
+        let status = bar.status();
 
-        let color = if bar.complete_flash > 0 {
-            if bar.complete_flash % 6 < 3 {
-                STATUS_SUCCESS
-            } else {
-                bar.color
+        if slot.height < 2 {
+            render_compact_bar(frame, bar, slot);
+            continue;
+        }
+
+        let mut left = vec![Span::styled(bar.label.as_str(), Style::default().fg(bar.color).bold())];
+        if let Some(stage) = bar.stage_label() {
+            left.push(Span::styled(" ▸ ", Style::default().fg(TEXT_DIM)));
+            left.push(Span::styled(stage, Style::default().fg(TEXT_PRIMARY)));
+        }
+
+        let right = match status {
+            Some((status, color)) => Span::styled(status, Style::default().fg(color).bold()),
+            None => {
+                let details = match bar.throughput() {
+                    Some(throughput) => format!("{throughput}  {}", bar.eta()),
+                    None => bar.eta(),
+                };
+                Span::styled(details, Style::default().fg(TEXT_DIM))
             }
-        } else {
-            bar.color
         };
 
-        let label = if bar.complete_flash > 0 {
-            format!("{} ✓ COMPLETE", bar.label)
-        } else {
-            format!("{} {:5.1}%", bar.label, bar.progress * 100.0)
-        };
+        let [details_row, meter] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(slot);
+        frame.render_widget(Line::from(left), details_row);
+        frame.render_widget(Line::from(right).right_aligned(), details_row);
+        render_meter(frame, bar, meter);
+    }
+}
+
+/// A bar on a single row: the gauge carries everything, other styles get
+/// the label beside them
+fn render_compact_bar(frame: &mut Frame, bar: &ProgressBar, area: Rect) {
+    let mut label = bar.label.clone();
+    if let Some(stage) = bar.stage_label().filter(|_| bar.status().is_none()) {
+        label = format!("{label} ▸ {stage}");
+    }
+    let label = match bar.status() {
+        Some((status, _)) => format!("{label} {status}"),
+        None if bar.style == ProgressStyle::Gauge => {
+            format!("{label} {:5.1}% {}", bar.progress * 100.0, bar.eta())
+        }
+        None => label,
+    };
+
+    if bar.style == ProgressStyle::Gauge {
+        render_gauge(frame, bar, label, area);
+        return;
+    }
+
+    let label_width = (label.chars().count() as u16 + 1).min(area.width / 2);
+    let [label_area, meter] = Layout::horizontal([Constraint::Length(label_width), Constraint::Fill(1)]).areas(area);
+    frame.render_widget(Span::styled(label, Style::default().fg(bar.color).bold()), label_area);
+    render_meter(frame, bar, meter);
+}
+
+/// The bar's progress in its style, using the rows it is given
+fn render_meter(frame: &mut Frame, bar: &ProgressBar, area: Rect) {
+    if area.is_empty() {
+        return;
+    }
+
+    match bar.style {
+        ProgressStyle::Gauge => render_gauge(frame, bar, percent(bar), area),
+        ProgressStyle::Blocks => render_rows(frame, bar, area, |width, _| {
+            // Two-cell segments with a gap, lit up to the progress
+            let segments = (width + 1) / 3;
+            let lit = (bar.progress * segments as f64).round() as usize;
+            (0..segments)
+                .map(|segment| {
+                    let gap = if segment + 1 < segments { " " } else { "" };
+                    if segment < lit {
+                        Span::styled(format!("██{gap}"), Style::default().fg(bar.gauge_color()))
+                    } else {
+                        Span::styled(format!("░░{gap}"), Style::default().fg(BORDER_DIM))
+                    }
+                })
+                .collect()
+        }),
+        ProgressStyle::Braille => render_rows(frame, bar, area, |width, row| {
+            // Two dot columns per cell; the bottom row keeps a dotted track
+            let dots = (bar.progress * width as f64 * 2.0).round() as usize;
+            let (full, half) = (dots / 2, dots % 2);
+            let track = if row + 1 == area.height as usize { "⣀" } else { "⠀" };
+            vec![
+                Span::styled("⣿".repeat(full), Style::default().fg(bar.gauge_color())),
+                Span::styled("⡇".repeat(half), Style::default().fg(bar.gauge_color())),
+                Span::styled(track.repeat(width - full - half), Style::default().fg(BORDER_DIM)),
+            ]
+        }),
+        ProgressStyle::Spinner => {
+            let spinner = match bar.status() {
+                Some((status, _)) => status.split(' ').next().unwrap_or("•"),
+                None => SPINNER_FRAMES[(bar.phase / 4) as usize % SPINNER_FRAMES.len()],
+            };
+            let row = middle_row(area);
+            let mut spans = vec![
+                Span::styled(format!("{spinner} "), Style::default().fg(bar.gauge_color()).bold()),
+                Span::styled(format!("{} ", percent(bar)), Style::default().fg(TEXT_HIGHLIGHT)),
+            ];
+            let used = spans.iter().map(Span::width).sum::<usize>();
+            spans.extend(thin_bar(bar, (row.width as usize).saturating_sub(used)));
+            frame.render_widget(Line::from(spans), row);
+        }
+        ProgressStyle::Radial => render_radial(frame, bar, area),
+        ProgressStyle::Packets => render_rows(frame, bar, area, |width, row| {
+            let filled = (bar.progress * width as f64).round() as usize;
+            (0..width)
+                .map(|x| {
+                    if x >= filled {
+                        return Span::styled("─", Style::default().fg(BORDER_DIM));
+                    }
+                    // Packets ride the filled part, each row a little behind
+                    // the one above
+                    let offset = (bar.phase / 3) as usize + PACKET_SPACING - row % PACKET_SPACING;
+                    if x % PACKET_SPACING == offset % PACKET_SPACING {
+                        Span::styled("■", Style::default().fg(TEXT_HIGHLIGHT).bold())
+                    } else {
+                        Span::styled("━", Style::default().fg(bar.gauge_color()))
+                    }
+                })
+                .collect()
+        }),
+    }
+}
+
+/// Rows of a text meter with the percentage at the end of the middle row;
+/// `row` draws the given row of the meter at the given width
+fn render_rows(frame: &mut Frame, bar: &ProgressBar, area: Rect, row: impl Fn(usize, usize) -> Vec<Span<'static>>) {
+    let percent = format!(" {}", percent(bar));
+    let width = (area.width as usize).saturating_sub(percent.chars().count());
+    let middle = middle_row(area);
 
-        let gauge = Gauge::default()
-            .ratio(bar.progress)
-            .gauge_style(Style::default().fg(color).bg(DARK_BG))
-            .label(label);
+    for (index, line_area) in area.rows().enumerate() {
+        let mut spans = row(width, index);
+        if line_area == middle {
+            spans.push(Span::styled(percent.clone(), Style::default().fg(TEXT_HIGHLIGHT)));
+        }
+        frame.render_widget(Line::from(spans), line_area);
+    }
+}
 
-        frame.render_widget(gauge, bar_area);
+/// A ring filling clockwise from twelve, with the percentage beside it;
+/// a quarter-circle glyph when there is only a row or two
+fn render_radial(frame: &mut Frame, bar: &ProgressBar, area: Rect) {
+    if area.height < 3 {
+        let glyph = RADIAL_GLYPHS[(bar.progress.clamp(0.0, 1.0) * 4.0) as usize];
+        let row = middle_row(area);
+        let mut spans = vec![
+            Span::styled(format!("{glyph} "), Style::default().fg(bar.gauge_color()).bold()),
+            Span::styled(format!("{} ", percent(bar)), Style::default().fg(TEXT_HIGHLIGHT)),
+        ];
+        let used = spans.iter().map(Span::width).sum::<usize>();
+        spans.extend(thin_bar(bar, (row.width as usize).saturating_sub(used)));
+        frame.render_widget(Line::from(spans), row);
+        return;
     }
+
+    // Cells are about twice as tall as wide, so a round ring is twice as
+    // many cells across as down
+    let ring_width = (area.height * 2).min(area.width);
+    let [ring, rest] = Layout::horizontal([Constraint::Length(ring_width), Constraint::Fill(1)]).areas(area);
+
+    let color = bar.gauge_color();
+    let arc: Vec<(f64, f64)> = (0..=(bar.progress.clamp(0.0, 1.0) * 240.0) as usize)
+        .flat_map(|step| {
+            let angle = step as f64 / 240.0 * TAU;
+            [1.0, 0.9, 0.8].map(|radius| (radius * angle.sin(), radius * angle.cos()))
+        })
+        .collect();
+    let canvas = Canvas::default()
+        .x_bounds([-1.1, 1.1])
+        .y_bounds([-1.1, 1.1])
+        .marker(symbols::Marker::Braille)
+        .paint(|ctx| {
+            ctx.draw(&Circle { x: 0.0, y: 0.0, radius: 0.9, color: BORDER_DIM });
+            ctx.layer();
+            ctx.draw(&Points { coords: &arc, color });
+        });
+    frame.render_widget(canvas, ring);
+
+    let row = middle_row(rest);
+    let mut spans = vec![Span::styled(format!(" {} ", percent(bar)), Style::default().fg(TEXT_HIGHLIGHT).bold())];
+    let used = spans.iter().map(Span::width).sum::<usize>();
+    spans.extend(thin_bar(bar, (row.width as usize).saturating_sub(used)));
+    frame.render_widget(Line::from(spans), row);
+}
+
+/// A one-line bar of heavy and light rules
+fn thin_bar(bar: &ProgressBar, width: usize) -> [Span<'static>; 2] {
+    let filled = ((bar.progress * width as f64).round() as usize).min(width);
+    [
+        Span::styled("━".repeat(filled), Style::default().fg(bar.gauge_color())),
+        Span::styled("─".repeat(width - filled), Style::default().fg(BORDER_DIM)),
+    ]
+}
+
+fn middle_row(area: Rect) -> Rect {
+    Rect { y: area.y + area.height.saturating_sub(1) / 2, height: 1.min(area.height), ..area }
+}
+
+fn percent(bar: &ProgressBar) -> String {
+    format!("{:5.1}%", bar.progress * 100.0)
+}
+
+fn render_gauge(frame: &mut Frame, bar: &ProgressBar, label: String, area: Rect) {
+    let gauge = Gauge::default()
+        .ratio(bar.progress.clamp(0.0, 1.0))
+        .gauge_style(Style::default().fg(bar.gauge_color()).bg(DARK_BG))
+        .label(label);
+
+    frame.render_widget(gauge, area);
 }
@@ -0,0 +1,115 @@
+use rand::Rng;
+use ratatui::prelude::*;
+
+use crate::ui::theme::*;
+use crate::TICKS_PER_SECOND;
+
+/// Length of the effect (3 seconds)
+const DURATION_TICKS: u64 = 3 * TICKS_PER_SECOND as u64;
+/// Characters scattered as static
+const STATIC_CHARS: [&str; 6] = ["░", "▒", "▓", "█", "▚", "▞"];
+const WARNING: &str = " ☢ SELF DESTRUCT SEQUENCE ☢ ";
+
+/// Full-screen flash, shake and static played over the dashboard
+pub struct SelfDestructState {
+    /// Ticks left to play; zero when idle
+    remaining: u64,
+    tick_counter: u64,
+}
+
+impl SelfDestructState {
+    pub fn new() -> Self {
+        Self {
+            remaining: 0,
+            tick_counter: 0,
+        }
+    }
+
+    pub fn start(&mut self) {
+        self.remaining = DURATION_TICKS;
+        self.tick_counter = 0;
+    }
+
+    pub fn stop(&mut self) {
+        self.remaining = 0;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.remaining > 0
+    }
+
+    pub fn tick(&mut self) {
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            self.tick_counter += 1;
+        }
+    }
+
+    /// How far the effect has played, 0.0 to 1.0
+    fn progress(&self) -> f64 {
+        self.tick_counter as f64 / DURATION_TICKS as f64
+    }
+}
+
+impl Default for SelfDestructState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shake, flash and scatter static over whatever was drawn
+pub fn render_self_destruct(frame: &mut Frame, state: &SelfDestructState, area: Rect) {
+    if !state.is_active() {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let progress = state.progress();
+    let buf = frame.buffer_mut();
+
+    // Shake: shift the whole screen sideways, calming down towards the end
+    let amplitude = if progress < 0.7 { 2 } else { 1 };
+    let shift: i32 = rng.gen_range(-amplitude..=amplitude);
+    if shift != 0 {
+        for y in area.top()..area.bottom() {
+            let row: Vec<_> = (area.left()..area.right()).map(|x| buf[(x, y)].clone()).collect();
+            for (offset, x) in (area.left()..area.right()).enumerate() {
+                let source = offset as i32 - shift;
+                match row.get(source as usize).filter(|_| source >= 0) {
+                    Some(cell) => buf[(x, y)] = cell.clone(),
+                    None => {
+                        buf[(x, y)].reset();
+                    }
+                }
+            }
+        }
+    }
+
+    // Flash: red background for 3 of every 12 ticks
+    let flash = state.tick_counter % 12 < 3;
+    // Static: more of the screen dissolves as it goes on
+    let density = 0.05 + 0.35 * progress;
+
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            if flash {
+                cell.set_bg(Color::Rgb(120, 0, 0));
+            }
+            if rng.gen_bool(density) {
+                let level = rng.gen_range(60..200);
+                cell.set_symbol(STATIC_CHARS[rng.gen_range(0..STATIC_CHARS.len())]);
+                cell.set_fg(Color::Rgb(level, level, level));
+            }
+        }
+    }
+
+    // Warning across the middle, blinking
+    if (state.tick_counter / 20).is_multiple_of(2) {
+        let width = (WARNING.chars().count() as u16).min(area.width);
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + area.height / 2;
+        let style = Style::default().fg(TEXT_HIGHLIGHT).bg(NEON_RED).bold();
+        buf.set_stringn(x, y, WARNING, width as usize, style);
+    }
+}
@@ -0,0 +1,179 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, BorderType, Paragraph, Sparkline},
+};
+use std::collections::VecDeque;
+
+use crate::data::system_stats::SystemStats;
+use crate::ui::theme::*;
+
+const HISTORY_SIZE: usize = 60;
+/// Critical temperature for sensors that do not report one, in °C
+const DEFAULT_CRITICAL: f64 = 90.0;
+/// A warning re-arms once the sensor is this much below critical
+const HYSTERESIS: f64 = 5.0;
+/// Widest label column
+const MAX_LABEL_WIDTH: usize = 18;
+
+/// One temperature sensor with its history
+pub struct Sensor {
+    pub label: String,
+    pub temperature: f64,
+    pub critical: f64,
+    pub history: VecDeque<u64>,
+    /// Above critical and already reported
+    pub alarmed: bool,
+}
+
+pub struct SensorsState {
+    pub sensors: Vec<Sensor>,
+    /// Warnings raised since the last `take_alerts`
+    alerts: Vec<String>,
+    tick_counter: u64,
+}
+
+impl SensorsState {
+    pub fn new() -> Self {
+        Self {
+            sensors: Vec::new(),
+            alerts: Vec::new(),
+            tick_counter: 0,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.tick_counter += 1;
+    }
+
+    pub fn update(&mut self, stats: &SystemStats) {
+        let mut previous = std::mem::take(&mut self.sensors);
+
+        for reading in &stats.sensors {
+            let mut sensor = match previous.iter().position(|s| s.label == reading.label) {
+                Some(index) => previous.swap_remove(index),
+                None => Sensor {
+                    label: reading.label.clone(),
+                    temperature: 0.0,
+                    critical: DEFAULT_CRITICAL,
+                    history: std::iter::repeat_n(0, HISTORY_SIZE).collect(),
+                    alarmed: false,
+                },
+            };
+
+            sensor.temperature = reading.temperature;
+            sensor.critical = reading.critical.unwrap_or(DEFAULT_CRITICAL);
+            if sensor.history.len() >= HISTORY_SIZE {
+                sensor.history.pop_front();
+            }
+            sensor.history.push_back(reading.temperature.max(0.0) as u64);
+
+            if !sensor.alarmed && sensor.temperature >= sensor.critical {
+                sensor.alarmed = true;
+                self.alerts.push(format!(
+                    "THERMAL WARNING: {} at {:.0}°C (critical {:.0}°C)",
+                    sensor.label, sensor.temperature, sensor.critical
+                ));
+            } else if sensor.alarmed && sensor.temperature < sensor.critical - HYSTERESIS {
+                sensor.alarmed = false;
+            }
+
+            self.sensors.push(sensor);
+        }
+    }
+
+    /// Thermal warnings raised since the last call
+    pub fn take_alerts(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.alerts)
+    }
+}
+
+impl Default for SensorsState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn render_sensors(frame: &mut Frame, state: &SensorsState, area: Rect) {
+    let alarmed = state.sensors.iter().any(|sensor| sensor.alarmed);
+    let blink = (state.tick_counter / 30).is_multiple_of(2);
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(BORDER_DIM))
+        .title(" SENSORS ")
+        .title_style(Style::default().fg(NEON_ORANGE).bold());
+    if alarmed {
+        block = block.title(
+            Line::styled(" ⚠ CRITICAL ", Style::default().fg(if blink { NEON_RED } else { TEXT_DIM }).bold())
+                .right_aligned(),
+        );
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner.height < 1 {
+        return;
+    }
+
+    if state.sensors.is_empty() {
+        render_no_telemetry(frame, blink, inner);
+        return;
+    }
+
+    let label_width = state
+        .sensors
+        .iter()
+        .map(|sensor| sensor.label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_LABEL_WIDTH)
+        .min(inner.width as usize / 3);
+
+    for (sensor, row) in state.sensors.iter().zip(inner.rows()) {
+        let color = temperature_color(sensor.temperature, sensor.critical);
+        let label: String = sensor.label.chars().take(label_width).collect();
+
+        let [label_area, value_area, spark_area] = Layout::horizontal([
+            Constraint::Length(label_width as u16 + 1),
+            Constraint::Length(7),
+            Constraint::Min(0),
+        ])
+        .areas(row);
+
+        frame.render_widget(
+            Span::styled(label, Style::default().fg(if sensor.alarmed { NEON_RED } else { TEXT_PRIMARY })),
+            label_area,
+        );
+        frame.render_widget(
+            Span::styled(format!("{:>5.1}°", sensor.temperature), Style::default().fg(color).bold()),
+            value_area,
+        );
+
+        // Newest samples that fit, on a scale up to the critical value
+        let data: Vec<u64> = sensor.history.iter().copied().collect();
+        let visible = &data[data.len().saturating_sub(spark_area.width as usize)..];
+        let sparkline = Sparkline::default()
+            .data(visible)
+            .max(sensor.critical.max(1.0) as u64)
+            .style(Style::default().fg(color));
+        frame.render_widget(sparkline, spark_area);
+    }
+}
+
+/// Placeholder for machines without temperature sensors
+fn render_no_telemetry(frame: &mut Frame, blink: bool, area: Rect) {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "◌ NO TELEMETRY ◌",
+            Style::default().fg(if blink { NEON_ORANGE } else { TEXT_DIM }).bold(),
+        )),
+        Line::from(Span::styled("sensor bus offline", Style::default().fg(TEXT_DIM))),
+    ];
+    lines.truncate(area.height as usize);
+
+    let top = area.y + area.height.saturating_sub(lines.len() as u16) / 2;
+    let text_area = Rect { y: top, height: lines.len() as u16, ..area };
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), text_area);
+}
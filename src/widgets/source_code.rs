@@ -32,7 +32,7 @@ impl SourceCodeState {
         self.tick_counter += 1;
 
         // Scroll every 15 ticks (~4 times per second at 60fps)
-        if self.tick_counter % 15 == 0 {
+        if self.tick_counter.is_multiple_of(15) {
             let current_lines: Vec<&str> = self.snippets[self.current_snippet].lines().collect();
 
             self.scroll_offset += 1;
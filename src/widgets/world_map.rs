@@ -1,22 +1,75 @@
 use ratatui::{
     prelude::*,
     widgets::{
-        canvas::{Canvas, Circle, Line, Map, MapResolution},
+        canvas::{Canvas, Circle, Context, Line, Map, MapResolution, Points},
         Block, Borders, BorderType,
     },
 };
-use rand::Rng;
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use chrono_tz::Tz;
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use std::{collections::HashMap, io};
 
+use crate::config::{MapConfig, Projection, RegionConfig};
+use crate::data::catalogue::{Catalogue, CatalogueEntry};
+use crate::data::live_connections::LiveSnapshot;
 use crate::ui::theme::*;
+use crate::TICKS_PER_SECOND;
 
-/// Major city nodes on the world map
+/// Number of segments used to draw a full great-circle arc
+const ARC_SEGMENTS: usize = 64;
+/// Packet travel speed in radians of arc per tick
+const PACKET_SPEED: f64 = 0.012;
+/// Decay of a node's arrival pulse per tick
+const PULSE_DECAY: f32 = 0.04;
+
+/// Number of random connections created at startup
+const INITIAL_CONNECTIONS: usize = 8;
+
+/// Narrowest viewport in degrees of longitude (maximum zoom)
+const MIN_VIEW_WIDTH: f64 = 10.0;
+/// Fraction of the remaining distance the view moves per tick after input
+const MANUAL_EASE: f64 = 0.3;
+/// Slower easing used when the auto-tour flies between regions
+const TOUR_EASE: f64 = 0.02;
+/// Sun altitude below which it is fully night (civil twilight ends)
+const TWILIGHT_DEGREES: f64 = -6.0;
+
+/// Built-in regions as (name, [west, east], [south, north])
+const BUILTIN_REGIONS: [(&str, [f64; 2], [f64; 2]); 7] = [
+    ("World", [-180.0, 180.0], [-90.0, 90.0]),
+    ("Europe", [-25.0, 45.0], [34.0, 72.0]),
+    ("North America", [-170.0, -50.0], [10.0, 75.0]),
+    ("South America", [-90.0, -30.0], [-57.0, 15.0]),
+    ("Africa", [-20.0, 55.0], [-36.0, 38.0]),
+    ("Middle East", [25.0, 65.0], [12.0, 42.0]),
+    ("Asia-Pacific", [60.0, 180.0], [-50.0, 55.0]),
+];
+
+/// City or facility node on the world map
 #[derive(Clone)]
 pub struct MapNode {
-    pub name: &'static str,
+    pub name: String,
+    pub label: String,
+    pub importance: f64,
     pub lat: f64,
     pub lon: f64,
+    /// Time zone for the local time; mean solar time if unknown
+    pub tz: Option<Tz>,
     pub active: bool,
     pub blink_phase: f32,
+    /// Arrival pulse intensity (1.0 = just arrived, 0.0 = none)
+    pub pulse: f32,
+}
+
+impl MapNode {
+    /// Local time at the node, e.g. "14:05"
+    pub fn local_time(&self, now: DateTime<Utc>) -> String {
+        match self.tz {
+            Some(tz) => now.with_timezone(&tz).format("%H:%M").to_string(),
+            None => (now + Duration::hours((self.lon / 15.0).round() as i64)).format("%H:%M").to_string(),
+        }
+    }
 }
 
 /// Connection between two nodes
@@ -26,92 +79,551 @@ pub struct Connection {
     pub to: usize,
     pub progress: f32,
     pub active: bool,
+    /// Positions of packets travelling along the arc (0.0 = source, 1.0 = destination)
+    pub packets: Vec<f32>,
+    /// Opened by a scenario cue rather than sampled, so live refreshes keep it
+    pub cued: bool,
+}
+
+impl Connection {
+    pub fn new(from: usize, to: usize, progress: f32, active: bool) -> Self {
+        Self {
+            from,
+            to,
+            progress,
+            active,
+            packets: Vec::new(),
+            cued: false,
+        }
+    }
+}
+
+/// Visible part of the map, in degrees
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    /// West and east edge
+    pub lon: [f64; 2],
+    /// South and north edge
+    pub lat: [f64; 2],
+}
+
+impl Viewport {
+    pub const WORLD: Self = Self {
+        lon: [-180.0, 180.0],
+        lat: [-90.0, 90.0],
+    };
+
+    fn width(&self) -> f64 {
+        self.lon[1] - self.lon[0]
+    }
+
+    fn height(&self) -> f64 {
+        self.lat[1] - self.lat[0]
+    }
+
+    /// Size relative to the whole world (1.0 = fully zoomed out)
+    pub fn scale(&self) -> f64 {
+        self.width() / 360.0
+    }
+
+    fn contains(&self, lat: f64, lon: f64) -> bool {
+        (self.lon[0]..=self.lon[1]).contains(&lon) && (self.lat[0]..=self.lat[1]).contains(&lat)
+    }
+
+    /// Same center, limited to the world and the maximum zoom
+    fn clamped(self) -> Self {
+        let width = self.width().clamp(MIN_VIEW_WIDTH, 360.0);
+        let height = self.height().clamp(MIN_VIEW_WIDTH / 2.0, 180.0);
+        let lon = ((self.lon[0] + self.lon[1]) / 2.0).clamp(-180.0 + width / 2.0, 180.0 - width / 2.0);
+        let lat = ((self.lat[0] + self.lat[1]) / 2.0).clamp(-90.0 + height / 2.0, 90.0 - height / 2.0);
+
+        Self {
+            lon: [lon - width / 2.0, lon + width / 2.0],
+            lat: [lat - height / 2.0, lat + height / 2.0],
+        }
+    }
+
+    /// Move `t` of the way towards another viewport
+    fn lerp(self, target: Self, t: f64) -> Self {
+        let mix = |a: f64, b: f64| a + (b - a) * t;
+        Self {
+            lon: [mix(self.lon[0], target.lon[0]), mix(self.lon[1], target.lon[1])],
+            lat: [mix(self.lat[0], target.lat[0]), mix(self.lat[1], target.lat[1])],
+        }
+    }
+}
+
+/// Named viewport that can be selected by key or config
+#[derive(Clone, Debug)]
+pub struct Region {
+    pub name: String,
+    pub view: Viewport,
+}
+
+impl Region {
+    fn from_config(config: &RegionConfig) -> io::Result<Self> {
+        let valid = |range: [f64; 2], limit: f64| -limit <= range[0] && range[0] < range[1] && range[1] <= limit;
+        if !valid(config.lon, 180.0) || !valid(config.lat, 90.0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid bounds for map region '{}'", config.name),
+            ));
+        }
+
+        Ok(Self {
+            name: config.name.clone(),
+            view: Viewport { lon: config.lon, lat: config.lat },
+        })
+    }
+}
+
+/// Connection counts shown in live mode
+#[derive(Clone, Copy, Default)]
+pub struct LiveStatus {
+    pub remote: usize,
+    pub local: usize,
+    pub unresolved: usize,
 }
 
 pub struct WorldMapState {
     pub nodes: Vec<MapNode>,
     pub connections: Vec<Connection>,
+    /// Set when showing real connections instead of the simulation;
+    /// node 0 is then the home node
+    pub live: Option<LiveStatus>,
+    /// Currently drawn viewport, easing towards `target`
+    pub view: Viewport,
+    target: Viewport,
+    ease: f64,
+    /// Selectable regions; the first one is the whole world
+    pub regions: Vec<Region>,
+    /// Region the view is heading to, unless panned or zoomed by hand
+    pub region: Option<usize>,
+    /// Is the auto-tour between busy regions running?
+    pub touring: bool,
+    tour_ticks: u64,
+    /// Current real or virtual time, for local times and the night side
+    pub time: DateTime<Utc>,
+    pub local_times: bool,
+    pub night: bool,
+    /// Draw the network on the flat map or on the globe
+    pub projection: Projection,
+    /// Node picked by key, highlighted and followed by the clock
+    pub selected: Option<usize>,
     tick_counter: u64,
 }
 
 impl WorldMapState {
     pub fn new() -> Self {
-        let nodes = vec![
-            MapNode { name: "NYC", lat: 40.7128, lon: -74.0060, active: true, blink_phase: 0.0 },
-            MapNode { name: "LON", lat: 51.5074, lon: -0.1278, active: true, blink_phase: 0.3 },
-            MapNode { name: "TYO", lat: 35.6762, lon: 139.6503, active: true, blink_phase: 0.6 },
-            MapNode { name: "SFO", lat: 37.7749, lon: -122.4194, active: false, blink_phase: 0.1 },
-            MapNode { name: "SYD", lat: -33.8688, lon: 151.2093, active: true, blink_phase: 0.4 },
-            MapNode { name: "MOW", lat: 55.7558, lon: 37.6173, active: false, blink_phase: 0.7 },
-            MapNode { name: "SHA", lat: 31.2304, lon: 121.4737, active: true, blink_phase: 0.2 },
-            MapNode { name: "BER", lat: 52.5200, lon: 13.4050, active: true, blink_phase: 0.5 },
-            MapNode { name: "DXB", lat: 25.2048, lon: 55.2708, active: false, blink_phase: 0.8 },
-            MapNode { name: "SIN", lat: 1.3521, lon: 103.8198, active: true, blink_phase: 0.9 },
-        ];
+        Self::from_entries(Catalogue::bundled().select(&[], MapConfig::default().max_nodes))
+    }
 
-        // Initial connections
-        let connections = vec![
-            Connection { from: 0, to: 1, progress: 1.0, active: true },  // NYC -> LON
-            Connection { from: 1, to: 7, progress: 1.0, active: true },  // LON -> BER
-            Connection { from: 7, to: 5, progress: 0.0, active: false }, // BER -> MOW
-            Connection { from: 2, to: 6, progress: 1.0, active: true },  // TYO -> SHA
-            Connection { from: 6, to: 9, progress: 0.5, active: true },  // SHA -> SIN
-            Connection { from: 9, to: 8, progress: 0.0, active: false }, // SIN -> DXB
-            Connection { from: 3, to: 0, progress: 1.0, active: true },  // SFO -> NYC
-            Connection { from: 4, to: 9, progress: 0.7, active: true },  // SYD -> SIN
-        ];
+    /// Build the map from the node catalogue; with a GeoIP database
+    /// configured, start in live mode with only the home node
+    pub fn with_config(config: &MapConfig, catalogue: &Catalogue) -> io::Result<Self> {
+        let entries = catalogue.select(&config.groups, config.max_nodes);
+        if entries.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("no map nodes in groups {:?}", config.groups),
+            ));
+        }
 
-        Self {
+        let mut state = if config.geoip.is_none() {
+            Self::from_entries(entries)
+        } else {
+            let home = match &config.home {
+                Some(label) => catalogue.find(label).cloned().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, format!("unknown home node '{label}'"))
+                })?,
+                None => entries[0].clone(),
+            };
+
+            let mut state = Self::from_entries(vec![home]);
+            state.nodes[0].active = true;
+            state.live = Some(LiveStatus::default());
+            state
+        };
+
+        for region in &config.regions {
+            state.regions.push(Region::from_config(region)?);
+        }
+
+        if let Some(name) = &config.viewport {
+            let index = state
+                .regions
+                .iter()
+                .position(|region| region.name.eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, format!("unknown map viewport '{name}'"))
+                })?;
+            state.select_region(index);
+            state.view = state.target;
+        }
+
+        state.local_times = config.local_times;
+        state.night = config.night;
+        state.projection = config.projection;
+        state.touring = config.tour;
+        state.tour_ticks = ((config.tour_secs.max(1.0) * TICKS_PER_SECOND as f32) as u64).max(1);
+        Ok(state)
+    }
+
+    fn from_entries(entries: Vec<CatalogueEntry>) -> Self {
+        let mut rng = rand::thread_rng();
+
+        let nodes = entries
+            .into_iter()
+            .map(|entry| MapNode {
+                name: entry.name,
+                label: entry.label,
+                importance: entry.importance,
+                lat: entry.lat,
+                lon: entry.lon,
+                tz: entry.tz,
+                active: entry.active,
+                blink_phase: rng.gen_range(0.0..1.0),
+                pulse: 0.0,
+            })
+            .collect();
+
+        let mut state = Self {
             nodes,
-            connections,
+            connections: Vec::new(),
+            live: None,
+            view: Viewport::WORLD,
+            target: Viewport::WORLD,
+            ease: MANUAL_EASE,
+            regions: BUILTIN_REGIONS
+                .iter()
+                .map(|&(name, lon, lat)| Region {
+                    name: name.to_string(),
+                    view: Viewport { lon, lat },
+                })
+                .collect(),
+            region: Some(0),
+            touring: false,
+            tour_ticks: (MapConfig::default().tour_secs * TICKS_PER_SECOND as f32) as u64,
+            time: Utc::now(),
+            local_times: true,
+            night: true,
+            projection: Projection::Flat,
+            selected: None,
             tick_counter: 0,
+        };
+
+        // Initial connections, some already established
+        for _ in 0..INITIAL_CONNECTIONS {
+            if let Some((from, to)) = state.random_pair() {
+                let progress = if rng.gen_bool(0.5) { 1.0 } else { rng.gen_range(0.0..1.0) };
+                state.connections.push(Connection::new(from, to, progress, true));
+            }
+        }
+
+        state
+    }
+
+    /// Replace the remote nodes with a new sample of live connections,
+    /// keeping the animation state of locations that are still connected;
+    /// the selection and cue-made links follow their nodes by name
+    pub fn apply_live(&mut self, snapshot: &LiveSnapshot) {
+        let (cued, sampled): (Vec<Connection>, Vec<Connection>) =
+            std::mem::take(&mut self.connections).into_iter().partition(|conn| conn.cued);
+        let mut old_connections: HashMap<usize, Connection> =
+            sampled.into_iter().map(|conn| (conn.to, conn)).collect();
+        let old_names: Vec<String> = self.nodes.iter().map(|node| node.name.clone()).collect();
+        let mut old_nodes = std::mem::take(&mut self.nodes).into_iter().enumerate();
+        let (_, home) = old_nodes.next().expect("live map has a home node");
+        let mut previous: HashMap<String, (MapNode, Option<Connection>)> = old_nodes
+            .map(|(index, node)| (node.name.clone(), (node, old_connections.remove(&index))))
+            .collect();
+
+        let mut rng = rand::thread_rng();
+        self.nodes.push(home);
+
+        for endpoint in &snapshot.endpoints {
+            let to = self.nodes.len();
+            let label = if endpoint.connections > 1 {
+                format!("{}×{}", endpoint.label, endpoint.connections)
+            } else {
+                endpoint.label.clone()
+            };
+
+            let (mut node, conn) = previous.remove(&endpoint.name).unwrap_or_else(|| {
+                let node = MapNode {
+                    name: endpoint.name.clone(),
+                    label: String::new(),
+                    importance: 0.0,
+                    lat: endpoint.lat,
+                    lon: endpoint.lon,
+                    tz: endpoint.tz,
+                    active: true,
+                    blink_phase: rng.gen_range(0.0..1.0),
+                    pulse: 0.0,
+                };
+                (node, None)
+            });
+            node.label = label;
+            node.importance = endpoint.connections as f64;
+            self.nodes.push(node);
+
+            let mut conn = conn.unwrap_or_else(|| Connection::new(0, to, 0.0, true));
+            conn.to = to;
+            self.connections.push(conn);
+        }
+
+        // Old index to new index, for nodes still on the map
+        let remap = |index: usize| self.nodes.iter().position(|node| node.name == old_names[index]);
+        self.selected = self.selected.and_then(remap);
+        let cued: Vec<Connection> = cued
+            .into_iter()
+            .filter_map(|conn| {
+                let (from, to) = (remap(conn.from)?, remap(conn.to)?);
+                Some(Connection { from, to, ..conn })
+            })
+            .collect();
+        self.connections.extend(cued);
+
+        self.live = Some(LiveStatus {
+            remote: snapshot.endpoints.iter().map(|e| e.connections).sum(),
+            local: snapshot.local,
+            unresolved: snapshot.unresolved,
+        });
+    }
+
+    pub fn update_time(&mut self, now: DateTime<Utc>) {
+        self.time = now;
+    }
+
+    /// Head for a named region; false if there is no such index
+    pub fn select_region(&mut self, index: usize) -> bool {
+        let Some(region) = self.regions.get(index) else {
+            return false;
+        };
+
+        self.target = region.view.clamped();
+        self.region = Some(index);
+        self.ease = MANUAL_EASE;
+        self.touring = false;
+        true
+    }
+
+    /// Select the next or previous node, most important first
+    pub fn select_node(&mut self, forward: bool) {
+        let mut order: Vec<usize> = (0..self.nodes.len()).collect();
+        order.sort_by(|&a, &b| self.nodes[b].importance.total_cmp(&self.nodes[a].importance));
+        if order.is_empty() {
+            return;
         }
+
+        let position = self.selected.and_then(|selected| order.iter().position(|&i| i == selected));
+        let next = match (position, forward) {
+            (None, true) => 0,
+            (None, false) => order.len() - 1,
+            (Some(i), true) => (i + 1) % order.len(),
+            (Some(i), false) => (i + order.len() - 1) % order.len(),
+        };
+        self.selected = Some(order[next]);
+    }
+
+    pub fn selected_node(&self) -> Option<&MapNode> {
+        self.selected.and_then(|index| self.nodes.get(index))
+    }
+
+    /// Index of the node with a name or label, ignoring case
+    pub fn find_node(&self, name: &str) -> Result<usize, String> {
+        self.nodes
+            .iter()
+            .position(|node| node.name.eq_ignore_ascii_case(name) || node.label.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("unknown map node '{name}'"))
+    }
+
+    /// Open a connection between two nodes, by name or label; an existing
+    /// one between them starts over
+    pub fn connect(&mut self, from: &str, to: &str) -> Result<(), String> {
+        let (from, to) = (self.find_node(from)?, self.find_node(to)?);
+
+        self.connections.retain(|conn| (conn.from, conn.to) != (from, to));
+        self.connections.push(Connection {
+            cued: true,
+            ..Connection::new(from, to, 0.0, true)
+        });
+        self.nodes[from].active = true;
+        self.nodes[to].active = true;
+        Ok(())
+    }
+
+    /// Pan by a fraction of the visible width and height (positive = east, north)
+    pub fn pan(&mut self, dx: f64, dy: f64) {
+        let (width, height) = (self.target.width(), self.target.height());
+        self.set_target(Viewport {
+            lon: [self.target.lon[0] + dx * width, self.target.lon[1] + dx * width],
+            lat: [self.target.lat[0] + dy * height, self.target.lat[1] + dy * height],
+        });
+    }
+
+    /// Zoom by `factor` (> 1 zooms in), keeping the point at the given
+    /// fractions of the view in place (0, 0 = top left corner)
+    pub fn zoom(&mut self, factor: f64, fx: f64, fy: f64) {
+        let view = self.target;
+        let (width, height) = (view.width() / factor, view.height() / factor);
+        let lon = view.lon[0] + fx * view.width();
+        let lat = view.lat[1] - fy * view.height();
+
+        self.set_target(Viewport {
+            lon: [lon - fx * width, lon + (1.0 - fx) * width],
+            lat: [lat - (1.0 - fy) * height, lat + fy * height],
+        });
+    }
+
+    pub fn toggle_projection(&mut self) {
+        self.projection = match self.projection {
+            Projection::Flat => Projection::Globe,
+            Projection::Globe => Projection::Flat,
+        };
+    }
+
+    pub fn toggle_tour(&mut self) {
+        self.touring = !self.touring;
+        if self.touring {
+            self.fly_to_next_region();
+        }
+    }
+
+    /// Manual pan and zoom stop the tour and leave the named regions
+    fn set_target(&mut self, view: Viewport) {
+        self.target = view.clamped();
+        self.region = None;
+        self.ease = MANUAL_EASE;
+        self.touring = false;
+    }
+
+    /// Tour step: the next region containing an end of an active
+    /// connection, or the whole world if nothing is going on
+    fn fly_to_next_region(&mut self) {
+        let busy: Vec<usize> = (1..self.regions.len())
+            .filter(|&index| {
+                let view = &self.regions[index].view;
+                self.connections
+                    .iter()
+                    .filter(|conn| conn.active && conn.progress > 0.0)
+                    .any(|conn| {
+                        [conn.from, conn.to]
+                            .iter()
+                            .any(|&i| view.contains(self.nodes[i].lat, self.nodes[i].lon))
+                    })
+            })
+            .collect();
+
+        let current = self.region.unwrap_or(0);
+        let next = busy
+            .iter()
+            .find(|&&index| index > current)
+            .or(busy.first())
+            .copied()
+            .unwrap_or(0);
+
+        self.target = self.regions[next].view.clamped();
+        self.region = Some(next);
+        self.ease = TOUR_EASE;
+    }
+
+    /// Pick two distinct nodes, weighted by importance
+    fn random_pair(&self) -> Option<(usize, usize)> {
+        if self.nodes.len() < 2 {
+            return None;
+        }
+
+        let weights = self.nodes.iter().map(|node| node.importance + 0.1);
+        let dist = WeightedIndex::new(weights).ok()?;
+        let mut rng = rand::thread_rng();
+
+        let from = dist.sample(&mut rng);
+        // Retry a few times instead of looping forever on degenerate weights
+        (0..8)
+            .map(|_| dist.sample(&mut rng))
+            .find(|&to| to != from)
+            .map(|to| (from, to))
     }
 
     pub fn tick(&mut self) {
         self.tick_counter += 1;
         let mut rng = rand::thread_rng();
 
+        if self.touring && self.tick_counter.is_multiple_of(self.tour_ticks) {
+            self.fly_to_next_region();
+        }
+        self.view = self.view.lerp(self.target, self.ease);
+        // Live mode shows real connections, so skip the random simulation
+        let simulated = self.live.is_none();
+
         // Update node blink phases
         for node in &mut self.nodes {
             node.blink_phase = (node.blink_phase + 0.05) % 1.0;
+            node.pulse = (node.pulse - PULSE_DECAY).max(0.0);
 
             // Randomly toggle active state
-            if rng.gen_bool(0.002) {
+            if simulated && rng.gen_bool(0.002) {
                 node.active = !node.active;
             }
         }
 
         // Update connections
+        let mut arrivals = Vec::new();
         for conn in &mut self.connections {
+            // Move packets along the arc; speed is constant over the globe,
+            // so longer arcs take longer to cross
+            let angle = central_angle(&self.nodes[conn.from], &self.nodes[conn.to]).max(0.01);
+            let step = (PACKET_SPEED / angle) as f32;
+            for packet in &mut conn.packets {
+                *packet += step;
+            }
+            let before = conn.packets.len();
+            conn.packets.retain(|&p| p < 1.0);
+            if conn.packets.len() < before {
+                arrivals.push(conn.to);
+            }
+
             if conn.active {
+                let was_complete = conn.progress >= 1.0;
                 conn.progress = (conn.progress + 0.02).min(1.0);
+                if conn.progress >= 1.0 && !was_complete {
+                    arrivals.push(conn.to);
+                }
+
+                // Send packets over established links, busier ones more often
+                let traffic = if simulated { 1.0 } else { self.nodes[conn.to].importance.min(5.0) };
+                if conn.progress >= 1.0 && rng.gen_bool((0.02 * traffic).min(1.0)) {
+                    conn.packets.push(0.0);
+                }
 
                 // Reset completed connections occasionally
-                if conn.progress >= 1.0 && rng.gen_bool(0.01) {
+                if simulated && conn.progress >= 1.0 && rng.gen_bool(0.01) {
                     conn.progress = 0.0;
                     conn.active = rng.gen_bool(0.7);
+                    conn.packets.clear();
                 }
             } else {
                 // Randomly activate inactive connections
-                if rng.gen_bool(0.005) {
+                if simulated && rng.gen_bool(0.005) {
                     conn.active = true;
                     conn.progress = 0.0;
                 }
             }
         }
 
-        // Occasionally add new random connection
+        for to in arrivals {
+            self.nodes[to].pulse = 1.0;
+        }
+
+        if !simulated {
+            return;
+        }
+
+        // Occasionally add new random connection between important nodes
         if rng.gen_bool(0.002) && self.connections.len() < 15 {
-            let from = rng.gen_range(0..self.nodes.len());
-            let to = rng.gen_range(0..self.nodes.len());
-            if from != to {
-                self.connections.push(Connection {
-                    from,
-                    to,
-                    progress: 0.0,
-                    active: true,
-                });
+            if let Some((from, to)) = self.random_pair() {
+                self.connections.push(Connection::new(from, to, 0.0, true));
             }
         }
 
@@ -128,27 +640,64 @@ impl Default for WorldMapState {
     }
 }
 
+/// Panel title shared by the flat map and the globe
+pub fn network_title(state: &WorldMapState) -> String {
+    match &state.live {
+        Some(live) => format!(
+            " LIVE NETWORK · {} REMOTE · {} LOCAL · {} UNKNOWN ",
+            live.remote, live.local, live.unresolved
+        ),
+        None => " GLOBAL NETWORK ".to_string(),
+    }
+}
+
 pub fn render_world_map(frame: &mut Frame, state: &WorldMapState, area: Rect) {
+    let mut title = network_title(state);
+    match state.region {
+        Some(0) => {}
+        Some(index) => title.push_str(&format!("· {} ", state.regions[index].name.to_uppercase())),
+        None => title.push_str(&format!("· {:.1}× ", 1.0 / state.view.scale())),
+    }
+    if state.touring {
+        title.push_str("· TOUR ");
+    }
+
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(BORDER_DIM))
-        .title(" GLOBAL NETWORK ")
+        .title(title)
         .title_style(Style::default().fg(MAP_NODE_ACTIVE).bold());
 
+    let view = state.view;
+    // Keep markers the same size on screen at every zoom level
+    let scale = view.scale();
+    let inner = block.inner(area);
+    let labels = place_labels(state, inner, (view.lon, view.lat), 2.0 * scale, |node| {
+        view.contains(node.lat, node.lon).then_some((node.lon, node.lat))
+    });
+
     let canvas = Canvas::default()
         .block(block)
-        .x_bounds([-180.0, 180.0])
-        .y_bounds([-90.0, 90.0])
+        .x_bounds(view.lon)
+        .y_bounds(view.lat)
         .marker(symbols::Marker::Braille)
         .paint(|ctx| {
+            // Shade the night side below everything else
+            if state.night {
+                let (night, twilight) = night_cells(state.time, view, inner);
+                ctx.draw(&Points { coords: &twilight, color: MAP_TWILIGHT });
+                ctx.draw(&Points { coords: &night, color: MAP_NIGHT });
+                ctx.layer();
+            }
+
             // Draw the world map
             ctx.draw(&Map {
                 resolution: MapResolution::High,
                 color: MAP_OUTLINE,
             });
 
-            // Draw connections
+            // Draw connections as great-circle arcs
             for conn in &state.connections {
                 if !conn.active && conn.progress == 0.0 {
                     continue;
@@ -156,51 +705,291 @@ pub fn render_world_map(frame: &mut Frame, state: &WorldMapState, area: Rect) {
 
                 let from = &state.nodes[conn.from];
                 let to = &state.nodes[conn.to];
+                let color = if conn.active { MAP_CONNECTION } else { BORDER_DIM };
 
-                // Calculate current endpoint based on progress
-                let current_lon = from.lon + (to.lon - from.lon) * conn.progress as f64;
-                let current_lat = from.lat + (to.lat - from.lat) * conn.progress as f64;
-
-                // Draw the connection line
-                ctx.draw(&Line {
-                    x1: from.lon,
-                    y1: from.lat,
-                    x2: current_lon,
-                    y2: current_lat,
-                    color: if conn.active { MAP_CONNECTION } else { BORDER_DIM },
-                });
+                draw_arc(ctx, from, to, conn.progress as f64, color);
+
+                // Draw packets travelling along the arc
+                for &packet in &conn.packets {
+                    let (lat, lon) = great_circle_point(from, to, packet as f64);
+                    ctx.draw(&Circle {
+                        x: lon,
+                        y: lat,
+                        radius: scale,
+                        color: TEXT_HIGHLIGHT,
+                    });
+                }
             }
 
             // Draw nodes
             for node in &state.nodes {
-                // Determine color based on active state and blink phase
-                let blink = (node.blink_phase * std::f32::consts::PI * 2.0).sin();
-                let color = if node.active {
-                    if blink > 0.0 {
-                        MAP_NODE_ACTIVE
-                    } else {
-                        Color::Rgb(0, 180, 180)
-                    }
-                } else {
-                    MAP_NODE_IDLE
-                };
+                let color = node_color(node);
+
+                // Draw arrival pulse as an expanding ring
+                if node.pulse > 0.0 {
+                    ctx.draw(&Circle {
+                        x: node.lon,
+                        y: node.lat,
+                        radius: (3.0 + (1.0 - node.pulse as f64) * 8.0) * scale,
+                        color: if node.pulse > 0.5 { TEXT_HIGHLIGHT } else { MAP_CONNECTION },
+                    });
+                }
 
                 // Draw node circle
                 ctx.draw(&Circle {
                     x: node.lon,
                     y: node.lat,
-                    radius: if node.active { 3.0 } else { 2.0 },
+                    radius: if node.active { 3.0 } else { 2.0 } * scale,
                     color,
                 });
+            }
 
-                // Draw node label
+            if let Some(node) = state.selected_node() {
+                ctx.draw(&Circle {
+                    x: node.lon,
+                    y: node.lat,
+                    radius: 5.0 * scale,
+                    color: NEON_YELLOW,
+                });
+            }
+
+            for label in &labels {
                 ctx.print(
-                    node.lon + 2.0,
-                    node.lat + 2.0,
-                    Span::styled(node.name, Style::default().fg(color)),
+                    label.x,
+                    label.y,
+                    Span::styled(label.text.clone(), Style::default().fg(label.color)),
                 );
             }
         });
 
     frame.render_widget(canvas, area);
 }
+
+/// Color of a node from its active state and blink phase
+pub fn node_color(node: &MapNode) -> Color {
+    let blink = (node.blink_phase * std::f32::consts::PI * 2.0).sin();
+    if !node.active {
+        MAP_NODE_IDLE
+    } else if blink > 0.0 {
+        MAP_NODE_ACTIVE
+    } else {
+        Color::Rgb(0, 180, 180)
+    }
+}
+
+/// A node label placed on the canvas
+pub struct PlacedLabel {
+    pub x: f64,
+    pub y: f64,
+    pub text: String,
+    pub color: Color,
+}
+
+/// Place node labels without overlaps, most important nodes first. Each
+/// label tries the right, left and below of its node and is dropped if
+/// none of them is free, which thins out crowded areas at low zoom.
+/// `project` gives a node's canvas position, or `None` if it is not visible.
+pub fn place_labels(
+    state: &WorldMapState,
+    inner: Rect,
+    (x_bounds, y_bounds): ([f64; 2], [f64; 2]),
+    offset: f64,
+    project: impl Fn(&MapNode) -> Option<(f64, f64)>,
+) -> Vec<PlacedLabel> {
+    if inner.width == 0 || inner.height == 0 {
+        return Vec::new();
+    }
+    // Canvas labels land on cell (x - left) * (width - 1) / (right - left)
+    let cell_width = (x_bounds[1] - x_bounds[0]) / (inner.width.max(2) - 1) as f64;
+    let cell_height = (y_bounds[1] - y_bounds[0]) / (inner.height.max(2) - 1) as f64;
+
+    // Selected and home first, then active nodes, then by importance
+    let mut order: Vec<(usize, (f64, f64))> = state
+        .nodes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, node)| Some((i, project(node)?)))
+        .collect();
+    order.sort_by(|&(a, _), &(b, _)| {
+        let (na, nb) = (&state.nodes[a], &state.nodes[b]);
+        let home = |i: usize| state.live.is_some() && i == 0;
+        let selected = |i: usize| state.selected == Some(i);
+        selected(b)
+            .cmp(&selected(a))
+            .then(home(b).cmp(&home(a)))
+            .then(nb.active.cmp(&na.active))
+            .then(nb.importance.total_cmp(&na.importance))
+    });
+
+    // Occupied (row, first column, last column) spans
+    let mut taken: Vec<(i64, i64, i64)> = Vec::new();
+    let mut labels = Vec::new();
+
+    for (index, (x, y)) in order {
+        let node = &state.nodes[index];
+        let mut text = node.label.clone();
+        if state.local_times {
+            text.push(' ');
+            text.push_str(&node.local_time(state.time));
+        }
+        if let Some(live) = state.live.filter(|live| index == 0 && live.local > 0) {
+            text.push_str(&format!(" ⌂{}", live.local));
+        }
+        let len = text.chars().count() as f64;
+
+        let candidates = [
+            (x + offset, y + offset),
+            (x - offset - len * cell_width, y + offset),
+            (x + offset, y - offset),
+        ];
+
+        let span = |(x, y): (f64, f64)| {
+            let col = ((x - x_bounds[0]) / cell_width).floor() as i64;
+            let row = ((y_bounds[1] - y) / cell_height).floor() as i64;
+            (row, col, col + len as i64 - 1)
+        };
+        let free = |(row, first, last): (i64, i64, i64)| {
+            first >= 0
+                && last < inner.width as i64
+                && (0..inner.height as i64).contains(&row)
+                && !taken.iter().any(|&(r, f, l)| r == row && first <= l + 1 && last + 1 >= f)
+        };
+
+        if let Some(&(x, y)) = candidates.iter().find(|&&pos| free(span(pos))) {
+            taken.push(span((x, y)));
+            labels.push(PlacedLabel {
+                x,
+                y,
+                text,
+                color: if state.selected == Some(index) { NEON_YELLOW } else { node_color(node) },
+            });
+        }
+    }
+
+    labels
+}
+
+/// Canvas coordinates as (x, y) pairs
+type Coords = Vec<(f64, f64)>;
+
+/// Centers of the canvas cells on the night side, split into full night
+/// and twilight, one braille dot per cell
+fn night_cells(time: DateTime<Utc>, view: Viewport, inner: Rect) -> (Coords, Coords) {
+    let (mut night, mut twilight) = (Vec::new(), Vec::new());
+    if inner.width == 0 || inner.height == 0 {
+        return (night, twilight);
+    }
+
+    let sun = subsolar_point(time);
+    let cell_width = view.width() / inner.width as f64;
+    let cell_height = view.height() / inner.height as f64;
+
+    for row in 0..inner.height {
+        let lat = view.lat[1] - (row as f64 + 0.5) * cell_height;
+        for col in 0..inner.width {
+            let lon = view.lon[0] + (col as f64 + 0.5) * cell_width;
+            let altitude = sun_altitude(sun, lat, lon);
+            if altitude < TWILIGHT_DEGREES {
+                night.push((lon, lat));
+            } else if altitude < 0.0 {
+                twilight.push((lon, lat));
+            }
+        }
+    }
+
+    (night, twilight)
+}
+
+/// Point where the sun is directly overhead, as (lat, lon), using the
+/// low-precision solar position from the Astronomical Almanac
+fn subsolar_point(time: DateTime<Utc>) -> (f64, f64) {
+    let seconds = time.num_seconds_from_midnight() as f64 + time.nanosecond() as f64 / 1e9;
+    // Days since J2000.0 (2000-01-01 12:00 UTC)
+    let days = (time.num_days_from_ce() - 730_120) as f64 + seconds / 86_400.0 - 0.5;
+
+    let mean_longitude = 280.460 + 0.985_647_4 * days;
+    let mean_anomaly = (357.528 + 0.985_600_3 * days).to_radians();
+    let ecliptic_longitude =
+        (mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin()).to_radians();
+    let obliquity = (23.439 - 0.000_000_4 * days).to_radians();
+
+    let declination = (obliquity.sin() * ecliptic_longitude.sin()).asin();
+    let right_ascension = (obliquity.cos() * ecliptic_longitude.sin()).atan2(ecliptic_longitude.cos());
+    let sidereal = 280.460_618_37 + 360.985_647_366_29 * days;
+
+    let lon = (right_ascension.to_degrees() - sidereal).rem_euclid(360.0);
+    (declination.to_degrees(), if lon > 180.0 { lon - 360.0 } else { lon })
+}
+
+/// Altitude of the sun above the horizon at a position, in degrees
+fn sun_altitude((sun_lat, sun_lon): (f64, f64), lat: f64, lon: f64) -> f64 {
+    let (lat, sun_lat) = (lat.to_radians(), sun_lat.to_radians());
+    let hour_angle = (lon - sun_lon).to_radians();
+    (lat.sin() * sun_lat.sin() + lat.cos() * sun_lat.cos() * hour_angle.cos())
+        .clamp(-1.0, 1.0)
+        .asin()
+        .to_degrees()
+}
+
+/// Unit vector on the sphere for a node's position
+fn to_vector(node: &MapNode) -> [f64; 3] {
+    let (lat, lon) = (node.lat.to_radians(), node.lon.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+/// Angle between two nodes as seen from the center of the Earth, in radians
+fn central_angle(from: &MapNode, to: &MapNode) -> f64 {
+    let (a, b) = (to_vector(from), to_vector(to));
+    let dot = a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+    dot.clamp(-1.0, 1.0).acos()
+}
+
+/// Point at fraction `t` along the great circle between two nodes, as (lat, lon)
+pub fn great_circle_point(from: &MapNode, to: &MapNode, t: f64) -> (f64, f64) {
+    let angle = central_angle(from, to);
+    if angle < 1e-9 {
+        return (from.lat, from.lon);
+    }
+
+    // Spherical linear interpolation between the two unit vectors
+    let (a, b) = (to_vector(from), to_vector(to));
+    let wa = ((1.0 - t) * angle).sin() / angle.sin();
+    let wb = (t * angle).sin() / angle.sin();
+    let p = [
+        wa * a[0] + wb * b[0],
+        wa * a[1] + wb * b[1],
+        wa * a[2] + wb * b[2],
+    ];
+
+    (p[2].atan2(p[0].hypot(p[1])).to_degrees(), p[1].atan2(p[0]).to_degrees())
+}
+
+/// Points along the first `progress` of the great-circle arc between two
+/// nodes, as (lat, lon), starting at `from`
+pub fn arc_points(from: &MapNode, to: &MapNode, progress: f64) -> Vec<(f64, f64)> {
+    let segments = ((ARC_SEGMENTS as f64 * progress).ceil() as usize).max(1);
+    (0..=segments)
+        .map(|i| great_circle_point(from, to, (i as f64 / ARC_SEGMENTS as f64).min(progress)))
+        .collect()
+}
+
+/// Draw the first `progress` of the great-circle arc between two nodes,
+/// wrapping segments that cross the antimeridian to the other map edge
+fn draw_arc(ctx: &mut Context, from: &MapNode, to: &MapNode, progress: f64, color: Color) {
+    for pair in arc_points(from, to, progress).windows(2) {
+        let ((lat1, lon1), (lat2, lon2)) = (pair[0], pair[1]);
+
+        if (lon2 - lon1).abs() > 180.0 {
+            // Crossing the antimeridian: split at the map edge
+            let edge = if lon1 > 0.0 { 180.0 } else { -180.0 };
+            let lon2_unwrapped = lon2 + 2.0 * edge;
+            let f = (edge - lon1) / (lon2_unwrapped - lon1);
+            let lat_edge = lat1 + (lat2 - lat1) * f;
+
+            ctx.draw(&Line { x1: lon1, y1: lat1, x2: edge, y2: lat_edge, color });
+            ctx.draw(&Line { x1: -edge, y1: lat_edge, x2: lon2, y2: lat2, color });
+        } else {
+            ctx.draw(&Line { x1: lon1, y1: lat1, x2: lon2, y2: lat2, color });
+        }
+    }
+}